@@ -0,0 +1,497 @@
+//! Per-run host CI reports for `implement`: every step with its command, duration,
+//! exit code and truncated output, plus the test cases of nextest's `junit.xml`,
+//! written as `JUnit` XML and JSON under the session's run directory.
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+    time::{Duration, SystemTime},
+};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339, macros::format_description};
+
+use crate::logging::{log_warn, strip_ansi};
+
+/// Run directories live under cargo's target directory, next to the build output.
+const RUNS_DIR: &str = "blueprints/runs";
+
+/// nextest tool config that turns on `junit.xml` for the active profile; relative to
+/// cargo's target directory.
+const NEXTEST_CONFIG: &str = "blueprints/nextest.toml";
+
+static TARGET_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Output kept per step; the end of cargo output is where the errors are.
+const MAX_OUTPUT_BYTES: usize = 16 * 1024;
+
+/// One `run_ci_checks` step.
+pub(crate) struct Step {
+    pub(crate) key: String,
+    pub(crate) command: String,
+    pub(crate) duration: Duration,
+    /// `None` when the process was terminated by a signal.
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) output: String,
+    /// Test cases from nextest's `junit.xml`, when the step produced one.
+    pub(crate) tests: Option<Vec<TestCase>>,
+}
+
+impl Step {
+    fn passed(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    fn exit(&self) -> String {
+        self.exit_code
+            .map_or("signal".to_string(), |code| code.to_string())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TestStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl TestStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct TestCase {
+    /// nextest binary ID, e.g. `demo` or `demo::test_vectors`.
+    pub(crate) suite: String,
+    pub(crate) name: String,
+    pub(crate) time: f64,
+    pub(crate) status: TestStatus,
+    pub(crate) message: Option<String>,
+    pub(crate) detail: Option<String>,
+}
+
+/// Numbered CI reports of one `implement` session.
+pub(crate) struct Recorder {
+    dir: PathBuf,
+    runs: usize,
+    /// Review cycle the next report belongs to.
+    pub(crate) review_cycle: usize,
+}
+
+impl Recorder {
+    pub(crate) fn new(module: &str) -> Self {
+        let stamp = OffsetDateTime::now_utc()
+            .format(format_description!(
+                "[year][month][day]T[hour][minute][second]Z"
+            ))
+            .unwrap_or_else(|_| "unknown".to_string());
+        let name: String = module
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        Self {
+            dir: target_dir()
+                .join(RUNS_DIR)
+                .join(format!("implement-{name}-{stamp}")),
+            runs: 0,
+            review_cycle: 0,
+        }
+    }
+
+    /// Write `ci-<n>.xml` and `ci-<n>.json` for one CI run; returns the JSON path.
+    pub(crate) fn write(
+        &mut self,
+        trigger: &str,
+        started: SystemTime,
+        steps: &[Step],
+    ) -> Result<PathBuf> {
+        self.runs += 1;
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+        let run = Run {
+            number: self.runs,
+            review_cycle: self.review_cycle,
+            trigger,
+            started: OffsetDateTime::from(started)
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+            steps,
+        };
+
+        let base = self.dir.join(format!("ci-{:03}", self.runs));
+        let xml = base.with_extension("xml");
+        fs::write(&xml, run.junit())
+            .with_context(|| format!("failed to write {}", xml.display()))?;
+        let json = base.with_extension("json");
+        fs::write(&json, serde_json::to_string_pretty(&run.json())?)
+            .with_context(|| format!("failed to write {}", json.display()))?;
+        Ok(json)
+    }
+}
+
+struct Run<'a> {
+    number: usize,
+    review_cycle: usize,
+    trigger: &'a str,
+    started: String,
+    steps: &'a [Step],
+}
+
+impl Run<'_> {
+    fn duration(&self) -> Duration {
+        self.steps.iter().map(|step| step.duration).sum()
+    }
+
+    fn json(&self) -> Value {
+        let steps: Vec<Value> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let (output, truncated) = truncate(&step.output);
+                json!({
+                    "step": step.key,
+                    "command": step.command,
+                    "status": if step.passed() { "pass" } else { "fail" },
+                    "exit_code": step.exit_code,
+                    "duration_ms": step.duration.as_millis(),
+                    "output": output,
+                    "output_truncated": truncated,
+                    "tests": step.tests.as_ref().map(|tests| {
+                        tests
+                            .iter()
+                            .map(|test| json!({
+                                "suite": test.suite,
+                                "name": test.name,
+                                "status": test.status.as_str(),
+                                "duration_ms": (test.time * 1000.0).round(),
+                                "message": test.message,
+                            }))
+                            .collect::<Vec<_>>()
+                    }),
+                })
+            })
+            .collect();
+        json!({
+            "run": self.number,
+            "review_cycle": self.review_cycle,
+            "trigger": self.trigger,
+            "started": self.started,
+            "status": if self.steps.iter().all(Step::passed) { "pass" } else { "fail" },
+            "duration_ms": self.duration().as_millis(),
+            "steps": steps,
+        })
+    }
+
+    /// One `<testsuite>` per step. Steps without parsed test cases become a single
+    /// test case named after the step, so dashboards see every step's health.
+    fn junit(&self) -> String {
+        let cases = |step: &Step| step.tests.as_ref().map_or(1, Vec::len);
+        let failures = |step: &Step| match &step.tests {
+            Some(tests) => tests
+                .iter()
+                .filter(|test| test.status == TestStatus::Failed)
+                .count(),
+            None => usize::from(!step.passed()),
+        };
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            out,
+            "<testsuites name=\"blueprints-ci-{:03}\" tests=\"{}\" failures=\"{}\" errors=\"0\" time=\"{:.3}\" timestamp=\"{}\">",
+            self.number,
+            self.steps.iter().map(cases).sum::<usize>(),
+            self.steps.iter().map(failures).sum::<usize>(),
+            self.duration().as_secs_f64(),
+            self.started
+        );
+        for step in self.steps {
+            let _ = writeln!(
+                out,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" time=\"{:.3}\" timestamp=\"{}\">",
+                escape(&step.key),
+                cases(step),
+                failures(step),
+                step.duration.as_secs_f64(),
+                self.started
+            );
+            out.push_str("    <properties>\n");
+            for (name, value) in [
+                ("review_cycle", self.review_cycle.to_string()),
+                ("trigger", self.trigger.to_string()),
+                ("command", step.command.clone()),
+                ("exit_code", step.exit()),
+            ] {
+                let _ = writeln!(
+                    out,
+                    "      <property name=\"{name}\" value=\"{}\"/>",
+                    escape(&value)
+                );
+            }
+            out.push_str("    </properties>\n");
+
+            if let Some(tests) = &step.tests {
+                for test in tests {
+                    test_case(&mut out, test);
+                }
+            } else {
+                step_case(&mut out, step);
+            }
+            let (output, _) = truncate(&step.output);
+            let _ = writeln!(
+                out,
+                "    <system-out>{}</system-out>\n  </testsuite>",
+                escape(&output)
+            );
+        }
+        out.push_str("</testsuites>\n");
+        out
+    }
+}
+
+fn step_case(out: &mut String, step: &Step) {
+    let _ = write!(
+        out,
+        "    <testcase name=\"{}\" classname=\"ci\" time=\"{:.3}\"",
+        escape(&step.key),
+        step.duration.as_secs_f64()
+    );
+    if step.passed() {
+        out.push_str("/>\n");
+    } else {
+        let _ = writeln!(
+            out,
+            ">\n      <failure message=\"{} failed (exit {})\"/>\n    </testcase>",
+            escape(&step.command),
+            step.exit()
+        );
+    }
+}
+
+fn test_case(out: &mut String, test: &TestCase) {
+    let _ = write!(
+        out,
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+        escape(&test.name),
+        escape(&test.suite),
+        test.time
+    );
+    let element = match test.status {
+        TestStatus::Passed => {
+            out.push_str("/>\n");
+            return;
+        }
+        TestStatus::Failed => "failure",
+        TestStatus::Skipped => "skipped",
+    };
+    out.push_str(">\n");
+    let message = test
+        .message
+        .as_deref()
+        .map(|message| format!(" message=\"{}\"", escape(message)))
+        .unwrap_or_default();
+    match &test.detail {
+        Some(detail) => {
+            let (detail, _) = truncate(detail);
+            let _ = writeln!(
+                out,
+                "      <{element}{message}>{}</{element}>",
+                escape(&detail)
+            );
+        }
+        None => {
+            let _ = writeln!(out, "      <{element}{message}/>");
+        }
+    }
+    out.push_str("    </testcase>\n");
+}
+
+/// The `--tool-config-file` value for `cargo nextest run`: a generated config that sets
+/// `junit.path` for the active profile, so no user nextest config is needed.
+pub(crate) fn nextest_tool_config() -> Result<String> {
+    // nextest wants an absolute path.
+    let path = env::current_dir()?.join(target_dir()).join(NEXTEST_CONFIG);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(
+        &path,
+        format!(
+            "[profile.{}.junit]\npath = \"junit.xml\"\n",
+            nextest_profile()
+        ),
+    )
+    .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(format!("blueprints:{}", path.display()))
+}
+
+/// Test cases from nextest's `junit.xml`, if the active profile wrote one after `since`.
+///
+/// nextest writes the report to `<target dir>/nextest/<profile>/junit.xml`; the profile
+/// is `NEXTEST_PROFILE` or `default`.
+pub(crate) fn nextest_cases(since: SystemTime) -> Option<Vec<TestCase>> {
+    let path = target_dir()
+        .join("nextest")
+        .join(nextest_profile())
+        .join("junit.xml");
+    let xml = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .filter(|modified| *modified >= since)
+        .and_then(|_| fs::read_to_string(&path).ok());
+    if xml.is_none() {
+        log_warn(format!(
+            "nextest wrote no {}; the CI report has no per-test results",
+            path.display()
+        ));
+    }
+    xml.map(|xml| parse_junit(&xml))
+}
+
+fn nextest_profile() -> String {
+    env::var("NEXTEST_PROFILE").unwrap_or_else(|_| "default".to_string())
+}
+
+/// Cargo's target directory, which honors `CARGO_TARGET_DIR` and `build.target-dir`.
+fn target_dir() -> &'static Path {
+    TARGET_DIR.get_or_init(|| {
+        Command::new("cargo")
+            .args(["metadata", "--format-version", "1", "--no-deps"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| serde_json::from_slice::<Value>(&output.stdout).ok())
+            .and_then(|metadata| metadata["target_directory"].as_str().map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("target"))
+    })
+}
+
+/// Minimal reader for the `<testsuite>`/`<testcase>` structure nextest emits.
+fn parse_junit(xml: &str) -> Vec<TestCase> {
+    let mut cases = Vec::new();
+    let mut suite = String::new();
+    let mut open: Option<TestCase> = None;
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        let after = &rest[start + 1..];
+        if let Some(cdata) = after.strip_prefix("![CDATA[") {
+            rest = cdata.find("]]>").map_or("", |end| &cdata[end + 3..]);
+            continue;
+        }
+        let Some(end) = after.find('>') else {
+            break;
+        };
+        let raw = &after[..end];
+        rest = &after[end + 1..];
+        let self_closing = raw.ends_with('/');
+        let raw = raw.trim_end_matches('/');
+        let (name, attrs) = raw.split_once(char::is_whitespace).unwrap_or((raw, ""));
+
+        match name {
+            "testsuite" => suite = attribute(attrs, "name").unwrap_or_default(),
+            "testcase" => {
+                let case = TestCase {
+                    suite: attribute(attrs, "classname").unwrap_or_else(|| suite.clone()),
+                    name: attribute(attrs, "name").unwrap_or_default(),
+                    time: attribute(attrs, "time")
+                        .and_then(|time| time.parse().ok())
+                        .unwrap_or(0.0),
+                    status: TestStatus::Passed,
+                    message: None,
+                    detail: None,
+                };
+                if self_closing {
+                    cases.push(case);
+                } else {
+                    open = Some(case);
+                }
+            }
+            "/testcase" => cases.extend(open.take()),
+            "failure" | "error" | "skipped" => {
+                let Some(case) = open.as_mut() else {
+                    continue;
+                };
+                case.status = if name == "skipped" {
+                    TestStatus::Skipped
+                } else {
+                    TestStatus::Failed
+                };
+                case.message = attribute(attrs, "message");
+                if !self_closing {
+                    let close = format!("</{name}>");
+                    if let Some(end) = rest.find(&close) {
+                        let detail = unescape(&rest[..end]);
+                        case.detail = (!detail.trim().is_empty()).then_some(detail);
+                        rest = &rest[end + close.len()..];
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    cases
+}
+
+fn attribute(attrs: &str, key: &str) -> Option<String> {
+    let mut rest = attrs;
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value.chars().next()?;
+        let value = &value[1..];
+        let end = value.find(quote)?;
+        if name == key {
+            return Some(unescape(&value[..end]));
+        }
+        rest = &value[end + 1..];
+    }
+    None
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&#13;", "\r")
+        .replace("&amp;", "&")
+}
+
+/// Text as XML content or attribute value, without ANSI colors or characters XML 1.0 forbids.
+fn escape(text: &str) -> String {
+    strip_ansi(text)
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The last `MAX_OUTPUT_BYTES` of `output`, and whether anything was cut.
+fn truncate(output: &str) -> (String, bool) {
+    if output.len() <= MAX_OUTPUT_BYTES {
+        return (output.to_string(), false);
+    }
+    let mut start = output.len() - MAX_OUTPUT_BYTES;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    (
+        format!("[... {start} bytes truncated ...]\n{}", &output[start..]),
+        true,
+    )
+}
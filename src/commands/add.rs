@@ -0,0 +1,186 @@
+use anyhow::{Result, anyhow};
+use clap::{Args, Subcommand};
+use std::{fmt::Write as _, path::Path};
+
+use super::common::prepare_blueprints;
+use crate::{
+    logging::log_blueprints,
+    records::{
+        BlueprintSet, IdKind, REQUIREMENTS_FILE, SPEC_FILE, id_sort_key, ids, insert_line,
+        lifecycle::Status, validate_field,
+    },
+};
+
+#[derive(Args, Debug)]
+pub struct AddArgs {
+    /// Workspace crate package name.
+    #[arg(
+        long = "crate",
+        value_name = "crate",
+        conflicts_with = "module_path",
+        global = true
+    )]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path", global = true)]
+    pub module_path: Option<String>,
+
+    #[command(subcommand)]
+    pub record: AddRecord,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AddRecord {
+    /// Insert an `R-### - <sentence>` line, keeping R-ids sorted ascending.
+    Requirement(RequirementArgs),
+    /// Append an `S-###[.n]` clause, keeping spec file order authoritative.
+    Spec(SpecArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RequirementArgs {
+    /// One sentence describing WHAT and WHY, ending with `.`, `!` or `?`.
+    pub sentence: String,
+}
+
+#[derive(Args, Debug)]
+pub struct SpecArgs {
+    /// Requirement IDs covered by the clause (repeat or comma-separate).
+    #[arg(
+        long = "r",
+        value_name = "R-###",
+        required = true,
+        value_delimiter = ','
+    )]
+    pub requirements: Vec<String>,
+
+    /// Imperative, testable behavior.
+    #[arg(long = "do", value_name = "statement")]
+    pub do_: String,
+
+    /// Concise, stable title (4-7 words).
+    #[arg(long)]
+    pub title: String,
+
+    /// Allocate an `S-###.n` sub-clause under this base spec ID.
+    #[arg(long, value_name = "S-###")]
+    pub parent: Option<String>,
+
+    /// Optional `IF:` field (APIs/types).
+    #[arg(long = "if", value_name = "apis")]
+    pub if_: Option<String>,
+
+    /// Optional `ER:` field (errors).
+    #[arg(long)]
+    pub er: Option<String>,
+
+    /// Optional `LM:` field (limits).
+    #[arg(long)]
+    pub lm: Option<String>,
+
+    /// Optional `OB:` field (observability).
+    #[arg(long)]
+    pub ob: Option<String>,
+}
+
+pub fn handle(args: &AddArgs) -> Result<()> {
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    // Held until the line is written so a concurrent `add` cannot take the same ID.
+    let _lock = ids::lock()?;
+    let set = BlueprintSet::load(blueprints.dir())?;
+
+    let (file, id, line, index) = match &args.record {
+        AddRecord::Requirement(requirement) => {
+            let sentence = validate_field("requirement", &requirement.sentence)?;
+            if !sentence.ends_with(['.', '!', '?']) {
+                return Err(anyhow!("requirement must end with '.', '!' or '?'"));
+            }
+            let id = ids::next_id(blueprints.dir(), IdKind::Requirement)?;
+            let line = format!("{id} - {sentence}");
+
+            // R lines are sorted ascending and a new ID is the highest ever used.
+            let index = set.requirements.last().map_or(usize::MAX, |r| r.line);
+            (REQUIREMENTS_FILE, id, line, index)
+        }
+        AddRecord::Spec(spec) => {
+            let (id, line) = spec_line(&set, blueprints.dir(), spec)?;
+
+            // S lines keep file order: append after the parent's clauses or the last clause.
+            let index = match spec.parent.as_deref() {
+                Some(parent) => {
+                    let prefix = format!("{parent}.");
+                    set.spec
+                        .clauses
+                        .iter()
+                        .filter(|clause| clause.id == parent || clause.id.starts_with(&prefix))
+                        .map(|clause| clause.line)
+                        .max()
+                        .unwrap_or(usize::MAX)
+                }
+                None => set.spec.clauses.last().map_or(usize::MAX, |c| c.line),
+            };
+            (SPEC_FILE, id, line, index)
+        }
+    };
+
+    insert_line(&blueprints.join(file), index, &line)?;
+    log_blueprints(format!("Inserted into {file}: {line}"));
+    println!("{id}");
+    Ok(())
+}
+
+fn spec_line(set: &BlueprintSet, dir: &Path, args: &SpecArgs) -> Result<(String, String)> {
+    let mut requirements = args.requirements.clone();
+    requirements.sort_by_key(|id| id_sort_key(id));
+    requirements.dedup();
+
+    for requirement in &requirements {
+        if IdKind::of(requirement) != Some(IdKind::Requirement) {
+            return Err(anyhow!("'{requirement}' is not a requirement ID (R-###)"));
+        }
+        if !set.defines(requirement) {
+            return Err(anyhow!(
+                "{requirement} is not defined in {REQUIREMENTS_FILE}"
+            ));
+        }
+        if set.status_of(requirement) == Status::Removed {
+            return Err(anyhow!("{requirement} is removed in the lifecycle ledger"));
+        }
+    }
+
+    let statement = validate_field("DO", &args.do_)?;
+    let title = validate_field("TITLE", &args.title)?;
+    let words = title.split_whitespace().count();
+    if !(4..=7).contains(&words) {
+        return Err(anyhow!("TITLE must be 4-7 words (got {words}): {title}"));
+    }
+
+    let id = match args.parent.as_deref() {
+        Some(parent) => {
+            if !set.defines(parent) {
+                return Err(anyhow!("{parent} is not defined in {SPEC_FILE}"));
+            }
+            ids::next_sub_id(dir, parent)?
+        }
+        None => ids::next_id(dir, IdKind::Spec)?,
+    };
+
+    let mut line = format!(
+        "{id} | R:{} | DO:{statement} | TITLE:{title}",
+        requirements.join(",")
+    );
+    // Canonical optional field order: IF, ER, LM, OB.
+    for (key, value) in [
+        ("IF", &args.if_),
+        ("ER", &args.er),
+        ("LM", &args.lm),
+        ("OB", &args.ob),
+    ] {
+        if let Some(value) = value {
+            let _ = write!(line, " | {key}:{}", validate_field(key, value)?);
+        }
+    }
+
+    Ok((id, line))
+}
@@ -0,0 +1,266 @@
+use anyhow::{Result, anyhow};
+use clap::Args;
+use std::thread;
+
+use super::{
+    check_history,
+    common::{
+        BlueprintsContext, Tokens, WorkflowConfig, describe_exit, list_macos_sound_names,
+        play_notification_chime_with, prepare_blueprints,
+    },
+};
+use crate::{
+    logging::log_blueprints,
+    policy::{Guard, Policy},
+    records::{
+        CONTRACTS_FILE, DELIVERY_PLAN_FILE, LIFECYCLE_FILE, REQUIREMENTS_FILE, SPEC_FILE,
+        TEST_VECTORS_FILE, read_optional,
+        rules::Rule,
+        schema::{self, Violation},
+    },
+};
+
+const BUILDER_PROMPT_TEMPLATE: &str = include_str!("../prompts/bootstrap/BUILDER.md");
+const REVIEWER_PROMPT_TEMPLATE: &str = include_str!("../prompts/bootstrap/REVIEWER.md");
+
+#[derive(Args, Debug, Clone)]
+pub struct BootstrapArgs {
+    /// Workspace crate package name.
+    #[arg(long = "crate", value_name = "crate", conflicts_with = "module_path")]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path")]
+    pub module_path: Option<String>,
+
+    /// macOS system sound name to play on success
+    #[arg(long)]
+    pub sound: Option<String>,
+
+    /// List available macOS system sounds and exit
+    #[arg(long)]
+    pub list_sounds: bool,
+}
+
+pub fn handle(args: &BootstrapArgs) -> Result<()> {
+    if args.list_sounds {
+        for name in list_macos_sound_names() {
+            println!("{name}");
+        }
+        return Ok(());
+    }
+    let sound = args.sound.as_deref();
+
+    let tokens = Tokens::new();
+    let config = WorkflowConfig::from_env()?;
+
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    for file in [
+        REQUIREMENTS_FILE,
+        SPEC_FILE,
+        CONTRACTS_FILE,
+        TEST_VECTORS_FILE,
+    ] {
+        if !read_optional(&blueprints.join(file))?.trim().is_empty() {
+            return Err(anyhow!(
+                "{} already exists; bootstrap only drafts blueprints for crates without them",
+                blueprints.join(file).display()
+            ));
+        }
+    }
+
+    let reviewer_prompt = blueprints.apply(tokens.apply(REVIEWER_PROMPT_TEMPLATE));
+    let builder_template = blueprints.apply(tokens.apply(BUILDER_PROMPT_TEMPLATE));
+
+    let mut reviewer_guard = Guard::new(Policy::read_only("reviewer"));
+    let mut builder_guard = Guard::new(builder_policy(&blueprints));
+
+    // The first builder pass drafts everything; later passes apply reviewer feedback.
+    let mut feedback = "none".to_string();
+    let mut review_cycle = 0usize;
+
+    loop {
+        if review_cycle >= config.max_reviewer_iters {
+            return Err(anyhow!(
+                "review cycles exceeded MAX_REVIEWER_ITERS={}",
+                config.max_reviewer_iters
+            ));
+        }
+        review_cycle += 1;
+
+        run_builder(
+            &blueprints,
+            &mut builder_guard,
+            &builder_template,
+            &tokens,
+            &feedback,
+        )?;
+        thread::sleep(config.loop_sleep);
+
+        log_blueprints("RUNNING REVIEWER AGENT");
+        let reviewer = reviewer_guard.run(
+            &[
+                "exec",
+                "--model",
+                "gpt-5",
+                "--config",
+                "model_reasoning_effort='high'",
+                "--sandbox",
+                "read-only",
+                "--full-auto",
+            ],
+            &reviewer_prompt,
+        )?;
+
+        if !reviewer.status.success() {
+            return Err(anyhow!(
+                "reviewer codex exec failed (exit {})",
+                describe_exit(reviewer.status)
+            ));
+        }
+
+        let reviewer_trimmed = reviewer.stdout.trim();
+
+        if reviewer_trimmed == tokens.error {
+            return Err(anyhow!("reviewer reported {}", tokens.error));
+        }
+
+        // Reviewer sign-off still has to pass the host-side schema checks.
+        if reviewer_trimmed == tokens.completed {
+            let violations = validate_drafts(&blueprints)?;
+            if violations.is_empty() {
+                log_blueprints(
+                    "Bootstrap complete; review the drafted 01-requirements.md before approving it (requirements are human-owned)",
+                );
+                play_notification_chime_with(sound);
+                return Ok(());
+            }
+
+            log_blueprints("Reviewer sign-off detected but drafts failed schema validation");
+            feedback = violations
+                .iter()
+                .enumerate()
+                .map(|(idx, violation)| format!("{}) {violation}", idx + 1))
+                .collect::<Vec<_>>()
+                .join("\n");
+            continue;
+        }
+
+        feedback = extract_continue_payload(&reviewer.stdout, &tokens).ok_or_else(|| {
+            anyhow!(
+                "reviewer must emit {} with actionable feedback",
+                tokens.continue_token
+            )
+        })?;
+
+        if feedback.is_empty() {
+            return Err(anyhow!(
+                "reviewer emitted no actionable feedback between control tokens"
+            ));
+        }
+
+        thread::sleep(config.loop_sleep);
+    }
+}
+
+/// Drafts plus `///` tag lines in the crate's sources; never the plan or lifecycle.
+fn builder_policy(blueprints: &BlueprintsContext) -> Policy {
+    Policy::allow_only("builder", [blueprints.dir().to_path_buf()])
+        .denying([
+            blueprints.join(DELIVERY_PLAN_FILE),
+            blueprints.join(LIFECYCLE_FILE),
+        ])
+        .tagging([blueprints.crate_dir().to_path_buf()])
+}
+
+fn run_builder(
+    blueprints: &BlueprintsContext,
+    guard: &mut Guard,
+    builder_template: &str,
+    tokens: &Tokens,
+    feedback: &str,
+) -> Result<()> {
+    let builder_prompt = builder_template.replace("${REVIEWER_FEEDBACK}", feedback);
+
+    log_blueprints("RUNNING BUILDER AGENT");
+    let builder = guard.run(
+        &[
+            "exec",
+            "--model",
+            "gpt-5-codex",
+            "--config",
+            "model_reasoning_effort='high'",
+            "--full-auto",
+        ],
+        &builder_prompt,
+    )?;
+
+    if !builder.status.success() {
+        return Err(anyhow!(
+            "builder codex exec failed (exit {})",
+            describe_exit(builder.status)
+        ));
+    }
+
+    if builder.last_stdout_line.trim() == tokens.error {
+        return Err(anyhow!("builder reported {}", tokens.error));
+    }
+    check_history::enforce(blueprints)?;
+
+    Ok(())
+}
+
+fn validate_drafts(blueprints: &BlueprintsContext) -> Result<Vec<Violation>> {
+    let requirements_content = read_optional(&blueprints.join(REQUIREMENTS_FILE))?;
+    let spec_content = read_optional(&blueprints.join(SPEC_FILE))?;
+    let contracts_content = read_optional(&blueprints.join(CONTRACTS_FILE))?;
+    let vectors_content = read_optional(&blueprints.join(TEST_VECTORS_FILE))?;
+
+    let mut violations = Vec::new();
+    for (file, content) in [
+        (REQUIREMENTS_FILE, &requirements_content),
+        (SPEC_FILE, &spec_content),
+        (TEST_VECTORS_FILE, &vectors_content),
+    ] {
+        if content.trim().is_empty() {
+            violations.push(Violation {
+                file,
+                line: 1,
+                rule: Rule::MissingFile,
+                message: "file is missing or empty".to_string(),
+            });
+        }
+    }
+
+    violations.extend(schema::check_all(
+        &requirements_content,
+        &spec_content,
+        &contracts_content,
+        &vectors_content,
+    ));
+
+    Ok(violations)
+}
+
+fn extract_continue_payload(output: &str, tokens: &Tokens) -> Option<String> {
+    let mut found = false;
+    let mut payload = Vec::new();
+
+    for raw in output.lines() {
+        let line = raw.trim_end_matches('\r');
+        if !found {
+            if line.trim() == tokens.continue_token {
+                found = true;
+                continue;
+            }
+            continue;
+        }
+        payload.push(line.to_string());
+    }
+
+    if found {
+        Some(payload.join("\n").trim().to_string())
+    } else {
+        None
+    }
+}
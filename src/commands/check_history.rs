@@ -0,0 +1,72 @@
+use anyhow::{Result, anyhow};
+use clap::Args;
+
+use super::common::{BlueprintsContext, prepare_blueprints};
+use crate::{
+    findings::{self, Finding, Format},
+    history,
+    logging::log_blueprints,
+};
+
+#[derive(Args, Debug)]
+pub struct CheckHistoryArgs {
+    /// Workspace crate package name.
+    #[arg(long = "crate", value_name = "crate", conflicts_with = "module_path")]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path")]
+    pub module_path: Option<String>,
+
+    /// Git revision to compare the blueprint files against.
+    #[arg(long, value_name = "rev", default_value = "HEAD")]
+    pub base: String,
+
+    /// Output format; `json` and `sarif` are written to stdout.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+pub fn handle(args: &CheckHistoryArgs) -> Result<()> {
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    let base = history::resolve(blueprints.dir(), &args.base)
+        .ok_or_else(|| anyhow!("base revision `{}` not found in git", args.base))?;
+
+    report(&blueprints, &args.base, &base, args.format)?;
+    if args.format != Format::Text {
+        return Ok(());
+    }
+    log_blueprints(format!(
+        "Blueprints keep append-only history and stable IDs against {}",
+        args.base
+    ));
+    Ok(())
+}
+
+/// Run after every builder iteration of the loop commands; skipped when `HEAD`
+/// does not resolve (no git repository or no commits yet).
+pub(crate) fn enforce(blueprints: &BlueprintsContext) -> Result<()> {
+    match history::resolve(blueprints.dir(), "HEAD") {
+        Some(base) => report(blueprints, "HEAD", &base, Format::Text),
+        None => Ok(()),
+    }
+}
+
+fn report(blueprints: &BlueprintsContext, name: &str, base: &str, format: Format) -> Result<()> {
+    let findings: Vec<Finding> = history::check(blueprints.dir(), base)?
+        .into_iter()
+        .map(|violation| Finding::of_violation(blueprints.dir(), violation))
+        .collect();
+    // Machine-readable output is written even when there is nothing to report.
+    if format != Format::Text || !findings.is_empty() {
+        findings::emit(&findings, format)?;
+    }
+    if findings.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "{} blueprint history violation(s) against {name}",
+        findings.len()
+    ))
+}
@@ -1,4 +1,8 @@
-use crate::logging::log_codex;
+use crate::{
+    logging::{self, log_blueprints, log_codex, log_debug, log_error, log_warn},
+    records::schema::Violation,
+    summarizer, tui,
+};
 use anyhow::{Context, Result, anyhow};
 use clap::ValueEnum;
 use std::{
@@ -6,7 +10,11 @@ use std::{
     io::{self, BufRead, BufReader, Read, Write},
     path::{Path, PathBuf},
     process::{Command, ExitStatus, Stdio},
-    sync::{OnceLock, mpsc},
+    sync::{
+        OnceLock,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
     thread,
     time::{Duration, Instant},
 };
@@ -15,7 +23,11 @@ pub(crate) const COMPLETED_TOKEN: &str = "__BLUEPRINTS_COMPLETED__";
 pub(crate) const CONTINUE_TOKEN: &str = "__BLUEPRINTS_CONTINUE__";
 pub(crate) const ERROR_TOKEN: &str = "__BLUEPRINTS_ERROR__";
 
-static SUMMARIZE_ENABLED: OnceLock<bool> = OnceLock::new();
+const NON_INTERACTIVE_PROMPT: &str = include_str!("../prompts/batch/NON_INTERACTIVE.md");
+
+static SUMMARIZE: OnceLock<SummarizeConfig> = OnceLock::new();
+/// Set after the first `--summarize=llm` failure; later chunks are printed raw.
+static LLM_SUMMARIZER_FAILED: AtomicBool = AtomicBool::new(false);
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "lower")]
@@ -24,12 +36,45 @@ pub(crate) enum WorkflowMode {
     Update,
 }
 
-pub(crate) fn set_summarize_enabled(enabled: bool) {
-    let _ = SUMMARIZE_ENABLED.set(enabled);
+/// How `--summarize` condenses agent output.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lower")]
+pub(crate) enum SummarizeMode {
+    /// Rule-based progress lines; no extra model calls.
+    Heuristic,
+    /// `codex exec --profile <summarizer profile>` per chunk.
+    Llm,
+}
+
+/// Global `--summarize*` settings; summaries are off when `mode` is `None`.
+#[derive(Clone, Debug)]
+pub(crate) struct SummarizeConfig {
+    pub(crate) mode: Option<SummarizeMode>,
+    /// How much agent output is collected into one summary.
+    pub(crate) interval: Duration,
+    /// Only the most recent `max_chunk_bytes` of a chunk are summarized.
+    pub(crate) max_chunk_bytes: usize,
+    /// Codex profile used by `--summarize=llm`.
+    pub(crate) profile: String,
+}
+
+impl Default for SummarizeConfig {
+    fn default() -> Self {
+        Self {
+            mode: None,
+            interval: Duration::from_secs(15),
+            max_chunk_bytes: 32 * 1024,
+            profile: "summarizer".to_string(),
+        }
+    }
+}
+
+pub(crate) fn set_summarize_config(config: SummarizeConfig) {
+    let _ = SUMMARIZE.set(config);
 }
 
-fn summarize_enabled() -> bool {
-    *SUMMARIZE_ENABLED.get_or_init(|| false)
+fn summarize_config() -> SummarizeConfig {
+    SUMMARIZE.get_or_init(SummarizeConfig::default).clone()
 }
 
 pub(crate) struct WorkflowConfig {
@@ -82,6 +127,7 @@ impl Tokens {
 pub(crate) struct BlueprintsContext {
     package: String,
     blueprints_dir: PathBuf,
+    crate_dir: PathBuf,
 }
 
 impl BlueprintsContext {
@@ -89,6 +135,15 @@ impl BlueprintsContext {
         &self.package
     }
 
+    pub(crate) fn dir(&self) -> &Path {
+        &self.blueprints_dir
+    }
+
+    /// Root of the crate that owns the blueprints (the directory with `Cargo.toml`).
+    pub(crate) fn crate_dir(&self) -> &Path {
+        &self.crate_dir
+    }
+
     pub(crate) fn join(&self, file: &str) -> PathBuf {
         self.blueprints_dir.join(file)
     }
@@ -160,9 +215,18 @@ pub(crate) fn prepare_blueprints(
     let (blueprints_dir, root_used) = locate_or_create_blueprints(&search_roots)?;
     let package = infer_package_name(crate_name, crate_root.as_ref(), root_used.as_path());
 
+    let crate_dir = crate_root.unwrap_or(root_used);
+    logging::set_crate(&package);
+    log_debug(format!(
+        "workspace {}, blueprints {}",
+        workspace_root.display(),
+        blueprints_dir.display()
+    ));
+
     Ok(BlueprintsContext {
         package,
         blueprints_dir,
+        crate_dir,
     })
 }
 
@@ -472,10 +536,12 @@ pub(crate) fn list_macos_sound_names() -> Vec<String> {
     Vec::new()
 }
 #[cfg(not(target_os = "macos"))]
+#[allow(dead_code)]
 pub(crate) fn resolve_macos_sound_path(_name: &str) -> Option<String> {
     None
 }
 
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
 fn run_quiet(cmd: &str, args: &[&str]) -> bool {
     Command::new(cmd)
         .args(args)
@@ -559,7 +625,9 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
         }
     }
 
+    log_debug(format!("codex {}", args.join(" ")));
     let mut child = codex_cmd
+        .env("BLUEPRINTS_RUN_ID", logging::run_id())
         .args(args)
         .arg(prompt)
         .arg("--skip-git-repo-check")
@@ -577,7 +645,9 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
         .take()
         .context("codex stderr pipe unavailable")?;
 
-    let do_summarize = summarize_enabled();
+    let summarize = summarize_config();
+    let do_summarize = summarize.mode.is_some();
+    let summary_interval = summarize.interval;
 
     let (summary_sender, summary_receiver) = if do_summarize {
         let (tx, rx) = mpsc::channel::<SummaryRequest>();
@@ -588,7 +658,7 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
     let (stream_tx, stream_rx) = mpsc::channel::<StreamPacket>();
 
     let summarizer_handle = summary_receiver.map(|summary_rx| {
-        thread::spawn(move || -> Result<()> {
+        thread::spawn(move || {
             while let Ok(request) = summary_rx.recv() {
                 let (chunk, final_update) = match request {
                     SummaryRequest::Interval(chunk) => (chunk, false),
@@ -599,7 +669,33 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
                     continue;
                 }
 
-                let summary = summarize_chunk(&chunk, final_update)?;
+                let capped = cap_chunk(&chunk, summarize.max_chunk_bytes);
+                let summary = match summarize.mode {
+                    Some(SummarizeMode::Heuristic) => {
+                        let tokens = Tokens::new();
+                        summarizer::summarize(
+                            capped,
+                            &[tokens.completed, tokens.continue_token, tokens.error],
+                        )
+                    }
+                    _ if LLM_SUMMARIZER_FAILED.load(Ordering::Relaxed) => {
+                        forward_raw(&chunk);
+                        continue;
+                    }
+                    _ => match summarize_chunk(capped, final_update, &summarize.profile) {
+                        Ok(summary) => summary,
+                        Err(err) => {
+                            // A flaky summarizer must not cost the agent run; show the whole raw
+                            // chunk, and the rest too rather than retrying a failing model call.
+                            log_warn(format!(
+                                "summarizer failed, showing raw output from now on: {err:#}"
+                            ));
+                            LLM_SUMMARIZER_FAILED.store(true, Ordering::Relaxed);
+                            forward_raw(&chunk);
+                            continue;
+                        }
+                    },
+                };
                 if summary.trim().is_empty() {
                     continue;
                 }
@@ -611,15 +707,12 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
                 }
                 io::stdout().flush().ok();
             }
-
-            Ok(())
         })
     });
 
     let summary_sender_for_aggregator = summary_sender.clone();
 
     let aggregator_handle = thread::spawn(move || -> Result<AggregatedOutput> {
-        let summary_interval = Duration::from_secs(15);
         let mut last_summary = Instant::now();
         let mut chunk_buffer = String::new();
         let mut stdout_capture = String::new();
@@ -641,7 +734,10 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
                         tx.send(SummaryRequest::Interval(chunk))
                             .map_err(|err| anyhow!(err))?;
                     } else {
-                        log_codex("Codex agent still running; no new output in the last 15s.");
+                        log_codex(format!(
+                            "Codex agent still running; no new output in the last {}s.",
+                            summary_interval.as_secs()
+                        ));
                         io::stdout().flush().ok();
                     }
                     last_summary = Instant::now();
@@ -679,7 +775,10 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
                             tx.send(SummaryRequest::Interval(chunk))
                                 .map_err(|err| anyhow!(err))?;
                         } else {
-                            log_codex("Codex agent still running; no new output in the last 15s.");
+                            log_codex(format!(
+                                "Codex agent still running; no new output in the last {}s.",
+                                summary_interval.as_secs()
+                            ));
                             io::stdout().flush().ok();
                         }
                         last_summary = Instant::now();
@@ -706,9 +805,13 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
                         let trimmed = chunk.trim_end_matches(&['\n', '\r'][..]);
                         last_stdout_line = trimmed.to_string();
                         chunk_buffer.push_str(&chunk);
-                        // forward to stdout
-                        let _ = io::stdout().write_all(chunk.as_bytes());
-                        let _ = io::stdout().flush();
+                        // forward to stdout (or the dashboard log)
+                        if tui::is_active() {
+                            tui::log(&chunk);
+                        } else {
+                            let _ = io::stdout().write_all(chunk.as_bytes());
+                            let _ = io::stdout().flush();
+                        }
                     }
                     Ok(StreamPacket::StderrChunk(chunk)) => {
                         stderr_capture.push_str(&chunk);
@@ -719,9 +822,13 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
                                 chunk_buffer.push('\n');
                             }
                         }
-                        // forward to stderr
-                        let _ = io::stderr().write_all(chunk.as_bytes());
-                        let _ = io::stderr().flush();
+                        // forward to stderr (or the dashboard log)
+                        if tui::is_active() {
+                            tui::log(&chunk);
+                        } else {
+                            let _ = io::stderr().write_all(chunk.as_bytes());
+                            let _ = io::stderr().flush();
+                        }
                     }
                     Ok(StreamPacket::StdoutClosed) => {
                         stdout_closed = true;
@@ -813,17 +920,23 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
         .join()
         .map_err(|_| anyhow!("summarizer aggregator thread panicked"))??;
 
-    if let Some(handle) = summarizer_handle {
-        let summarizer_result = handle
-            .join()
-            .map_err(|_| anyhow!("summarizer thread panicked"))?;
-        summarizer_result?;
+    if let Some(handle) = summarizer_handle
+        && handle.join().is_err()
+    {
+        log_error("summarizer thread panicked; agent output was not fully summarized");
     }
 
+    tui::record_usage(&aggregated.stdout);
+    tui::record_usage(&aggregated.stderr);
+
     if do_summarize && !status.success() && !aggregated.stderr.trim().is_empty() {
-        let mut stderr_handle = io::stderr().lock();
-        stderr_handle.write_all(aggregated.stderr.as_bytes())?;
-        stderr_handle.flush().ok();
+        if tui::is_active() {
+            tui::log(&aggregated.stderr);
+        } else {
+            let mut stderr_handle = io::stderr().lock();
+            stderr_handle.write_all(aggregated.stderr.as_bytes())?;
+            stderr_handle.flush().ok();
+        }
     }
 
     Ok(CommandOutput {
@@ -833,7 +946,33 @@ pub(crate) fn run_codex(args: &[&str], prompt: &str) -> Result<CommandOutput> {
     })
 }
 
-fn summarize_chunk(chunk: &str, final_update: bool) -> Result<String> {
+/// The most recent `max_bytes` of `chunk`, cut at a line start where possible.
+fn cap_chunk(chunk: &str, max_bytes: usize) -> &str {
+    if chunk.len() <= max_bytes {
+        return chunk;
+    }
+    let mut start = chunk.len() - max_bytes;
+    while !chunk.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = &chunk[start..];
+    tail.find('\n')
+        .filter(|&newline| newline + 1 < tail.len())
+        .map_or(tail, |newline| &tail[newline + 1..])
+}
+
+/// Print an unsummarized chunk the way verbatim streaming mode would.
+fn forward_raw(chunk: &str) {
+    if tui::is_active() {
+        tui::log(chunk);
+    } else {
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(chunk.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+fn summarize_chunk(chunk: &str, final_update: bool, profile: &str) -> Result<String> {
     let mut instructions = "Summarize the Codex agent activity for the user as a single concise sentence or short paragraph. Focus on concrete actions, omit control tokens, and do not use bullet points."
         .to_string();
     if final_update {
@@ -845,7 +984,7 @@ fn summarize_chunk(chunk: &str, final_update: bool) -> Result<String> {
     let prompt = format!("{instructions}\n\n<output_chunk>\n{chunk}\n</output_chunk>");
 
     let output = Command::new("codex")
-        .args(["exec", "--profile", "summarizer"])
+        .args(["exec", "--profile", profile])
         .arg(prompt)
         .arg("--skip-git-repo-check")
         .stdout(Stdio::piped())
@@ -877,6 +1016,81 @@ fn summarize_chunk(chunk: &str, final_update: bool) -> Result<String> {
     Ok(summary)
 }
 
+/// Read a `--brief` file up front, before `prepare_blueprints` changes the working directory.
+pub(crate) fn read_brief(path: &Path) -> Result<String> {
+    let brief = fs::read_to_string(path)
+        .with_context(|| format!("failed to read brief {}", path.display()))?;
+    if brief.trim().is_empty() {
+        return Err(anyhow!("brief {} is empty", path.display()));
+    }
+    Ok(brief)
+}
+
+/// Run an interactive design/update prompt headlessly through `codex exec`.
+///
+/// The non-interactive overrides and the brief are appended to `prompt`; the agent
+/// must not ask questions and signals failure with the ERROR token.
+pub(crate) fn run_non_interactive(prompt: &str, brief: Option<&str>) -> Result<()> {
+    let tokens = Tokens::new();
+    let brief = brief.map_or(
+        "(none provided; work from the existing blueprint files)",
+        str::trim,
+    );
+    let overrides = tokens
+        .apply(NON_INTERACTIVE_PROMPT)
+        .replace("${BRIEF}", brief);
+    let prompt = format!("{prompt}\n\n{overrides}");
+
+    logging::set_step("agent", 1);
+    log_blueprints("RUNNING NON-INTERACTIVE AGENT");
+    let output = run_codex(
+        &[
+            "exec",
+            "--model",
+            "gpt-5",
+            "--config",
+            "model_reasoning_effort='high'",
+            "--full-auto",
+        ],
+        &prompt,
+    )?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "codex exec failed (exit {})",
+            describe_exit(output.status)
+        ));
+    }
+
+    if output.last_stdout_line.trim() == tokens.error {
+        return Err(anyhow!("agent reported {}", tokens.error));
+    }
+
+    Ok(())
+}
+
+/// Fail with every schema violation logged when a blueprint file is missing or invalid.
+pub(crate) fn ensure_schema_valid(path: &Path, violations: &[Violation]) -> Result<()> {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    if content.trim().is_empty() {
+        return Err(anyhow!("{} was not written", path.display()));
+    }
+
+    if violations.is_empty() {
+        log_blueprints(format!("{} passed schema validation", path.display()));
+        return Ok(());
+    }
+
+    for violation in violations {
+        log_error(violation.to_string());
+    }
+    Err(anyhow!(
+        "{} failed schema validation ({} violation(s))",
+        path.display(),
+        violations.len()
+    ))
+}
+
 pub(crate) fn describe_exit(status: ExitStatus) -> String {
     status.code().map_or_else(
         || "terminated by signal".to_string(),
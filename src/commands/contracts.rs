@@ -1,19 +1,35 @@
-use anyhow::{Context, Result};
-use clap::Args;
-use std::process::{Command, Stdio};
+use anyhow::{Context, Result, anyhow};
+use clap::{Args, Subcommand};
+use std::{
+    path::PathBuf,
+    process::{Command, Stdio},
+};
 
-use super::common::{list_macos_sound_names, play_notification_chime_with, prepare_blueprints};
+use super::common::{
+    BlueprintsContext, ensure_schema_valid, find_workspace_root, list_macos_sound_names,
+    play_notification_chime_with, prepare_blueprints, read_brief, run_non_interactive,
+};
+use crate::{
+    contract_check::{self, CheckOutcome},
+    logging::{log_blueprints, log_error},
+    records::{CONTRACTS_FILE, contracts, read_optional, schema},
+};
 
 const DESIGN_PROMPT: &str = include_str!("../prompts/contracts/DESIGN.md");
 
 #[derive(Args, Debug, Clone)]
 pub struct ContractsArgs {
     /// Workspace crate package name.
-    #[arg(long = "crate", value_name = "crate", conflicts_with = "module_path")]
+    #[arg(
+        long = "crate",
+        value_name = "crate",
+        conflicts_with = "module_path",
+        global = true
+    )]
     pub crate_name: Option<String>,
 
     /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
-    #[arg(long = "module", value_name = "module-path")]
+    #[arg(long = "module", value_name = "module-path", global = true)]
     pub module_path: Option<String>,
 
     /// macOS system sound name to play on success
@@ -23,6 +39,23 @@ pub struct ContractsArgs {
     /// List available macOS system sounds and exit
     #[arg(long)]
     pub list_sounds: bool,
+
+    /// Run headlessly via `codex exec`, using this file as the human's answers
+    #[arg(long, value_name = "file")]
+    pub brief: Option<PathBuf>,
+
+    /// Run headlessly via `codex exec` instead of opening an interactive session
+    #[arg(long)]
+    pub non_interactive: bool,
+
+    #[command(subcommand)]
+    pub action: Option<ContractsAction>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ContractsAction {
+    /// Compile the `rust` code blocks of every contract with `cargo check`.
+    Check,
 }
 
 pub fn handle(args: &ContractsArgs) -> Result<()> {
@@ -32,11 +65,28 @@ pub fn handle(args: &ContractsArgs) -> Result<()> {
         }
         return Ok(());
     }
+    if let Some(ContractsAction::Check) = args.action {
+        let blueprints =
+            prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+        return check(&blueprints);
+    }
+
     let sound = args.sound.as_deref();
+    let brief = args.brief.as_deref().map(read_brief).transpose()?;
     let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
 
     let prompt = blueprints.apply(DESIGN_PROMPT);
 
+    if args.non_interactive || brief.is_some() {
+        run_non_interactive(&prompt, brief.as_deref())?;
+
+        let path = blueprints.join(CONTRACTS_FILE);
+        let content = read_optional(&path)?;
+        ensure_schema_valid(&path, &schema::check_contracts(&content))?;
+        play_notification_chime_with(sound);
+        return Ok(());
+    }
+
     let status = Command::new("codex")
         .args([
             "--model",
@@ -58,3 +108,38 @@ pub fn handle(args: &ContractsArgs) -> Result<()> {
 
     std::process::exit(status.code().unwrap_or(1));
 }
+
+fn check(blueprints: &BlueprintsContext) -> Result<()> {
+    let path = blueprints.join(CONTRACTS_FILE);
+    let parsed = contracts::parse(&read_optional(&path)?);
+    let workspace_root = find_workspace_root()?;
+
+    log_blueprints(format!(
+        "Compiling contract code blocks from {} with cargo check",
+        path.display()
+    ));
+    match contract_check::check(
+        &parsed,
+        blueprints.module(),
+        blueprints.crate_dir(),
+        &workspace_root,
+    )? {
+        CheckOutcome::NoBlocks => {
+            log_blueprints("No `rust` code blocks found in any contract; nothing to check.");
+            Ok(())
+        }
+        CheckOutcome::Checked(diagnostics) if diagnostics.is_empty() => {
+            log_blueprints("All contract code blocks compile.");
+            Ok(())
+        }
+        CheckOutcome::Checked(diagnostics) => {
+            for diagnostic in &diagnostics {
+                log_error(format!("{}:{diagnostic}", path.display()));
+            }
+            Err(anyhow!(
+                "{} compile error(s) in {CONTRACTS_FILE}",
+                diagnostics.len()
+            ))
+        }
+    }
+}
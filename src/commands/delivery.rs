@@ -2,11 +2,18 @@ use anyhow::{Result, anyhow};
 use clap::Args;
 use std::thread;
 
-use super::common::{
-    Tokens, WorkflowConfig, describe_exit, list_macos_sound_names, play_notification_chime_with,
-    prepare_blueprints, run_codex,
+use super::{
+    check_history,
+    common::{
+        BlueprintsContext, Tokens, WorkflowConfig, describe_exit, list_macos_sound_names,
+        play_notification_chime_with, prepare_blueprints,
+    },
+};
+use crate::{
+    logging::log_blueprints,
+    policy::{Guard, Policy},
+    records::DELIVERY_PLAN_FILE,
 };
-use crate::logging::log_blueprints;
 
 const BUILDER_PROMPT_TEMPLATE: &str = include_str!("../prompts/delivery/BUILDER.md");
 const REVIEWER_PROMPT_TEMPLATE: &str = include_str!("../prompts/delivery/REVIEWER.md");
@@ -45,6 +52,11 @@ pub fn handle(args: &DeliveryArgs) -> Result<()> {
     let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
     let reviewer_prompt = blueprints.apply(tokens.apply(REVIEWER_PROMPT_TEMPLATE));
     let builder_template = blueprints.apply(tokens.apply(BUILDER_PROMPT_TEMPLATE));
+    let mut reviewer_guard = Guard::new(Policy::read_only("reviewer"));
+    let mut builder_guard = Guard::new(Policy::allow_only(
+        "builder",
+        [blueprints.join(DELIVERY_PLAN_FILE)],
+    ));
 
     let mut review_cycle = 0usize;
 
@@ -57,7 +69,7 @@ pub fn handle(args: &DeliveryArgs) -> Result<()> {
         }
         review_cycle += 1;
 
-        let reviewer = run_codex(
+        let reviewer = reviewer_guard.run(
             &[
                 "exec",
                 "--model",
@@ -106,13 +118,22 @@ pub fn handle(args: &DeliveryArgs) -> Result<()> {
             ));
         }
 
-        run_builder_workflow(&builder_template, &tokens, &clean_feedback, &config)?;
+        run_builder_workflow(
+            &blueprints,
+            &mut builder_guard,
+            &builder_template,
+            &tokens,
+            &clean_feedback,
+            &config,
+        )?;
 
         thread::sleep(config.loop_sleep);
     }
 }
 
 fn run_builder_workflow(
+    blueprints: &BlueprintsContext,
+    guard: &mut Guard,
     builder_template: &str,
     tokens: &Tokens,
     clean_feedback: &str,
@@ -129,7 +150,7 @@ fn run_builder_workflow(
         builder_iter += 1;
 
         let builder_prompt = builder_template.replace("${REVIEWER_FEEDBACK}", clean_feedback);
-        let builder = run_codex(
+        let builder = guard.run(
             &[
                 "exec",
                 "--model",
@@ -153,6 +174,7 @@ fn run_builder_workflow(
         if builder_last == tokens.error {
             return Err(anyhow!("builder reported {}", tokens.error));
         }
+        check_history::enforce(blueprints)?;
 
         if builder_last == tokens.completed {
             return Ok(());
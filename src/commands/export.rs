@@ -0,0 +1,559 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    common::{BlueprintsContext, prepare_blueprints},
+    progress,
+};
+use crate::{
+    evidence,
+    logging::log_blueprints,
+    records::{
+        BlueprintSet, CONTRACTS_FILE, IdKind, LIFECYCLE_FILE, REQUIREMENTS_FILE, SPEC_FILE,
+        TEST_VECTORS_FILE, id_tokens,
+        lifecycle::{self, Status},
+        read_optional,
+    },
+    tags::{self, Tag},
+};
+
+/// Inline stylesheet; the page must render offline with no external assets.
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 0 auto; max-width: 72rem; padding: 1rem 2rem; color: #1f2328; }
+nav { position: sticky; top: 0; background: #fff; border-bottom: 1px solid #d0d7de; padding: .5rem 0; }
+nav a { margin-right: 1rem; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+h2 { border-bottom: 1px solid #d0d7de; padding-bottom: .25rem; margin-top: 2.5rem; }
+.record { border: 1px solid #d0d7de; border-radius: 6px; padding: .5rem .75rem; margin: .5rem 0; }
+.record:target { border-color: #0969da; box-shadow: 0 0 0 2px #54aeff66; }
+.record.removed { opacity: .6; }
+.record p { margin: .35rem 0; }
+.id { font-family: ui-monospace, monospace; font-weight: 600; }
+.meta { color: #59636e; font-size: .9em; }
+.undefined { color: #cf222e; text-decoration: underline wavy; }
+.badge { display: inline-block; border-radius: 1em; padding: 0 .6em; margin-left: .4em; font-size: .8em; border: 1px solid; }
+.badge.ok { color: #1a7f37; border-color: #1a7f37; }
+.badge.warn { color: #9a6700; border-color: #9a6700; }
+.badge.deprecated { color: #bc4c00; border-color: #bc4c00; }
+.badge.removed { color: #cf222e; border-color: #cf222e; }
+pre { background: #f6f8fa; padding: .5rem; overflow-x: auto; }
+table { border-collapse: collapse; }
+td, th { padding: .2rem .75rem .2rem 0; text-align: left; }
+.bar { display: inline-block; width: 12rem; height: .7rem; background: #eaeef2; border-radius: 4px; vertical-align: middle; }
+.bar span { display: block; height: 100%; background: #1a7f37; border-radius: 4px; }
+";
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Workspace crate package name.
+    #[arg(
+        long = "crate",
+        value_name = "crate",
+        conflicts_with = "module_path",
+        global = true
+    )]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path", global = true)]
+    pub module_path: Option<String>,
+
+    #[command(subcommand)]
+    pub format: ExportFormat,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ExportFormat {
+    /// Write a self-contained HTML site of the blueprints with cross-links and status.
+    Html(HtmlArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct HtmlArgs {
+    /// Directory that receives `index.html`; created if missing.
+    #[arg(long, value_name = "dir")]
+    pub out: PathBuf,
+}
+
+pub fn handle(args: &ExportArgs) -> Result<()> {
+    // `prepare_blueprints` moves to the workspace root; `--out` is relative to the caller.
+    let cwd = env::current_dir().context("failed to determine current working directory")?;
+    let ctx = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    match &args.format {
+        ExportFormat::Html(html) => export_html(&ctx, &cwd.join(&html.out)),
+    }
+}
+
+fn export_html(ctx: &BlueprintsContext, out: &Path) -> Result<()> {
+    let set = BlueprintSet::load(ctx.dir())?;
+    let tags = tags::scan_crate(ctx.crate_dir())?;
+    let page = Site::new(ctx, &set, &tags)?.render();
+
+    fs::create_dir_all(out).with_context(|| format!("failed to create {}", out.display()))?;
+    let path = out.join("index.html");
+    fs::write(&path, page).with_context(|| format!("failed to write {}", path.display()))?;
+    log_blueprints(format!("Wrote {}", path.display()));
+    Ok(())
+}
+
+struct Site<'a> {
+    module: &'a str,
+    set: &'a BlueprintSet,
+    tags: &'a [Tag],
+    /// Text shown for each record, also scanned for the IDs it references.
+    texts: BTreeMap<String, String>,
+    /// Records that mention each ID, in file order.
+    referenced_by: BTreeMap<String, Vec<String>>,
+    /// Record anchors already written; a duplicated ID only anchors its first card.
+    anchors: RefCell<BTreeSet<String>>,
+}
+
+impl<'a> Site<'a> {
+    fn new(ctx: &'a BlueprintsContext, set: &'a BlueprintSet, tags: &'a [Tag]) -> Result<Self> {
+        let read = |file| read_optional(&ctx.join(file));
+        let requirements = read(REQUIREMENTS_FILE)?;
+        let requirements: Vec<&str> = requirements.lines().collect();
+        let spec = read(SPEC_FILE)?;
+        let spec: Vec<&str> = spec.lines().collect();
+        let contracts = read(CONTRACTS_FILE)?;
+        let contracts: Vec<&str> = contracts.lines().collect();
+        let test_vectors = read(TEST_VECTORS_FILE)?;
+        let test_vectors: Vec<&str> = test_vectors.lines().collect();
+
+        let line = |lines: &[&str], number: usize| {
+            lines
+                .get(number - 1)
+                .map_or(String::new(), |line| line.trim_end().to_string())
+        };
+        let mut texts = BTreeMap::new();
+        for requirement in &set.requirements {
+            let text = line(&requirements, requirement.line);
+            let sentence = text.split_once(" - ").map_or("", |(_, rest)| rest);
+            texts.insert(requirement.id.clone(), sentence.to_string());
+        }
+        for clause in &set.spec.clauses {
+            texts.insert(clause.id.clone(), line(&spec, clause.line));
+        }
+        for contract in &set.contracts {
+            texts.insert(contract.id.clone(), section(&contracts, contract.line));
+        }
+        for tv in &set.test_vectors {
+            texts.insert(tv.id.clone(), line(&test_vectors, tv.line));
+        }
+        for item in &set.delivery {
+            texts.insert(item.id.clone(), item.text.clone());
+        }
+
+        let mut referenced_by: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for id in record_ids(set) {
+            for (_, token) in id_tokens(&texts[id]) {
+                let sources = referenced_by.entry(token.to_string()).or_default();
+                if token != id && !sources.iter().any(|source| source == id) {
+                    sources.push(id.clone());
+                }
+            }
+        }
+
+        Ok(Self {
+            module: ctx.module(),
+            set,
+            tags,
+            texts,
+            referenced_by,
+            anchors: RefCell::default(),
+        })
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{0} blueprints</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+             <h1>{0} blueprints</h1>\n<nav><a href=\"#overview\">Overview</a>\
+             <a href=\"#requirements\">Requirements</a><a href=\"#spec\">Spec</a>\
+             <a href=\"#contracts\">Contracts</a><a href=\"#test-vectors\">Test vectors</a>\
+             <a href=\"#delivery\">Delivery plan</a><a href=\"#lifecycle\">Lifecycle</a></nav>\n",
+            escape(self.module)
+        );
+
+        self.overview(&mut out);
+        self.requirements(&mut out);
+        self.spec(&mut out);
+        self.contracts(&mut out);
+        self.test_vectors(&mut out);
+        self.delivery(&mut out);
+        self.lifecycle(&mut out);
+
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn overview(&self, out: &mut String) {
+        let set = self.set;
+        let active = |id: &String| set.status_of(id) != Status::Removed;
+        let count = |ids: Vec<&String>, done: &dyn Fn(&str) -> bool| {
+            let total = ids.len();
+            (ids.into_iter().filter(|id| done(id)).count(), total)
+        };
+
+        let requirements: Vec<&String> = set.requirements.iter().map(|r| &r.id).collect();
+        let clauses: Vec<&String> = set.spec.clauses.iter().map(|c| &c.id).collect();
+        let vectors: Vec<&String> = set.test_vectors.iter().map(|tv| &tv.id).collect();
+        let rows = [
+            (
+                "Requirements covered by a spec clause",
+                count(
+                    requirements.into_iter().filter(|id| active(id)).collect(),
+                    &|id| self.specified(id),
+                ),
+            ),
+            (
+                "Spec clauses with test vectors",
+                count(
+                    clauses.iter().copied().filter(|id| active(id)).collect(),
+                    &|id| self.vectors_of(id) > 0,
+                ),
+            ),
+            (
+                "Spec clauses with tagged code",
+                count(
+                    clauses.into_iter().filter(|id| active(id)).collect(),
+                    &|id| evidence::missing_evidence(self.tags, id).is_none(),
+                ),
+            ),
+            (
+                "Test vectors with tagged tests",
+                count(
+                    vectors.into_iter().filter(|id| active(id)).collect(),
+                    &|id| evidence::missing_evidence(self.tags, id).is_none(),
+                ),
+            ),
+            (
+                "Delivery plan items done",
+                progress::completion(&set.delivery),
+            ),
+        ];
+
+        out.push_str("<h2 id=\"overview\">Overview</h2>\n<h3>Coverage</h3>\n<table>\n");
+        for (label, (done, total)) in rows {
+            row(out, label, done, total);
+        }
+        out.push_str("</table>\n");
+
+        let milestones = progress::milestones(&set.delivery);
+        if !milestones.is_empty() {
+            out.push_str("<h3>Progress by milestone</h3>\n<table>\n");
+            for (title, done, total) in milestones {
+                row(out, &self.linked(title), done, total);
+            }
+            out.push_str("</table>\n");
+        }
+    }
+
+    fn requirements(&self, out: &mut String) {
+        out.push_str("<h2 id=\"requirements\">Requirements</h2>\n");
+        for requirement in &self.set.requirements {
+            let id = &requirement.id;
+            let coverage = if self.specified(id) {
+                badge("ok", "specified")
+            } else {
+                badge("warn", "no spec clause")
+            };
+            let body = format!("<p>{}</p>", self.linked(&self.texts[id]));
+            self.record(out, id, &coverage, &body);
+        }
+    }
+
+    fn spec(&self, out: &mut String) {
+        out.push_str("<h2 id=\"spec\">Spec</h2>\n");
+        for clause in &self.set.spec.clauses {
+            let id = &clause.id;
+            let mut coverage = if self.vectors_of(id) > 0 {
+                badge("ok", "has test vectors")
+            } else {
+                badge("warn", "no test vector")
+            };
+            coverage.push_str(&implemented(self.tags, id));
+
+            let mut body = String::new();
+            if let Some(title) = clause.title() {
+                let _ = write!(body, "<p><strong>{}</strong></p>", escape(title));
+            }
+            let _ = write!(
+                body,
+                "<p class=\"meta\">Requirements: {}</p>",
+                self.id_list(&clause.requirements)
+            );
+            for (key, value) in clause.fields.iter().filter(|(key, _)| key != "TITLE") {
+                let _ = write!(body, "<p>{}: {}</p>", escape(key), self.linked(value));
+            }
+            self.record(out, id, &coverage, &body);
+        }
+    }
+
+    fn contracts(&self, out: &mut String) {
+        out.push_str("<h2 id=\"contracts\">Contracts</h2>\n");
+        for contract in &self.set.contracts {
+            let id = &contract.id;
+            let coverage = implemented(self.tags, id);
+            let body = format!("<pre>{}</pre>", self.linked(&self.texts[id]));
+            self.record(out, id, &coverage, &body);
+        }
+    }
+
+    fn test_vectors(&self, out: &mut String) {
+        out.push_str("<h2 id=\"test-vectors\">Test vectors</h2>\n");
+        for tv in &self.set.test_vectors {
+            let id = &tv.id;
+            let coverage = if evidence::missing_evidence(self.tags, id).is_none() {
+                badge("ok", "tested")
+            } else {
+                badge("warn", "untested")
+            };
+            let levels: Vec<String> = tv.levels.iter().map(char::to_string).collect();
+            let mut body = format!(
+                "<p class=\"meta\">Requirements: {} &middot; Spec: {} &middot; Levels: {}</p>\
+                 <p>GIVEN {}</p><p>WHEN {}</p><p>THEN {}</p>",
+                self.id_list(&tv.requirements),
+                self.id_list(&tv.specs),
+                levels.join(", "),
+                self.linked(&tv.given),
+                self.linked(&tv.when),
+                self.linked(&tv.then)
+            );
+            for (key, value) in &tv.extra {
+                let _ = write!(body, "<p>{}: {}</p>", escape(key), self.linked(value));
+            }
+            self.record(out, id, &coverage, &body);
+        }
+    }
+
+    fn delivery(&self, out: &mut String) {
+        out.push_str("<h2 id=\"delivery\">Delivery plan</h2>\n");
+        let mut milestone = None;
+        for item in &self.set.delivery {
+            if item.milestone.as_deref() != milestone {
+                milestone = item.milestone.as_deref();
+                if let Some(title) = milestone {
+                    let _ = writeln!(out, "<h3>{}</h3>", self.linked(title));
+                }
+            }
+            let state = if item.done {
+                badge("ok", "done")
+            } else {
+                badge("warn", "open")
+            };
+            let body = format!("<p>{}</p>", self.linked(&item.text));
+            self.record(out, &item.id, &state, &body);
+        }
+        let blocked = progress::blocked(self.set);
+        if !blocked.is_empty() {
+            out.push_str("<h3>Blocked</h3>\n<ul>\n");
+            for (item, reasons) in blocked {
+                let _ = writeln!(
+                    out,
+                    "<li>{}: {}</li>",
+                    link(&item.id),
+                    escape(&reasons.join(", "))
+                );
+            }
+            out.push_str("</ul>\n");
+        }
+    }
+
+    fn lifecycle(&self, out: &mut String) {
+        out.push_str("<h2 id=\"lifecycle\">Lifecycle</h2>\n");
+        if self.set.lifecycle.is_empty() {
+            let _ = writeln!(out, "<p class=\"meta\">No entries in {LIFECYCLE_FILE}.</p>");
+            return;
+        }
+        out.push_str(
+            "<table>\n<tr><th>ID</th><th>Status</th><th>Reason</th><th>Effective</th><th>Replaced by</th></tr>\n",
+        );
+        for record in &self.set.lifecycle {
+            let _ = writeln!(
+                out,
+                "<tr id=\"lifecycle-{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                record.line,
+                self.linked(&record.id),
+                record.status,
+                self.linked(&record.reason),
+                escape(record.effective.as_deref().unwrap_or("")),
+                self.linked(record.replace_by.as_deref().unwrap_or(""))
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    /// One record card: anchor, lifecycle and coverage badges, body, backlinks and code.
+    fn record(&self, out: &mut String, id: &str, coverage: &str, body: &str) {
+        let status = self.set.status_of(id);
+        let lifecycle = match lifecycle::latest(&self.set.lifecycle, id) {
+            Some(record) if status != Status::Active => format!(
+                "<a class=\"badge {status}\" href=\"#lifecycle-{}\">{status}</a>",
+                record.line
+            ),
+            _ => String::new(),
+        };
+        // Coverage says little about retired records.
+        let coverage = if status == Status::Removed {
+            ""
+        } else {
+            coverage
+        };
+        let anchor = if self.anchors.borrow_mut().insert(id.to_string()) {
+            format!(" id=\"{id}\"")
+        } else {
+            String::new()
+        };
+        let _ = write!(
+            out,
+            "<div class=\"record {status}\"{anchor}>\n<div><a class=\"id\" href=\"#{id}\">{id}</a>{lifecycle}{coverage}</div>\n{body}\n"
+        );
+
+        if let Some(sources) = self.referenced_by.get(id).filter(|s| !s.is_empty()) {
+            let _ = writeln!(
+                out,
+                "<p class=\"meta\">Referenced by: {}</p>",
+                self.id_list(sources)
+            );
+        }
+        let code: Vec<String> = self
+            .tags
+            .iter()
+            .filter(|tag| tag.ids.iter().any(|tagged| tagged == id))
+            .map(|tag| {
+                let item = tag
+                    .item
+                    .as_ref()
+                    .map_or("module".to_string(), |item| item.name.clone());
+                format!(
+                    "<code>{}:{}</code> {} (@{})",
+                    escape(&tag.file.display().to_string()),
+                    tag.line,
+                    escape(&item),
+                    tag.kind.name()
+                )
+            })
+            .collect();
+        if !code.is_empty() {
+            let _ = writeln!(out, "<p class=\"meta\">Code: {}</p>", code.join(", "));
+        }
+        out.push_str("</div>\n");
+    }
+
+    /// Whether an active spec clause covers requirement `id`.
+    fn specified(&self, id: &str) -> bool {
+        self.set.spec.clauses.iter().any(|clause| {
+            clause.requirements.iter().any(|r| r == id)
+                && self.set.status_of(&clause.id) != Status::Removed
+        })
+    }
+
+    fn vectors_of(&self, clause: &str) -> usize {
+        self.set
+            .test_vectors
+            .iter()
+            .filter(|tv| tv.specs.iter().any(|spec| spec == clause))
+            .count()
+    }
+
+    fn id_list(&self, ids: &[String]) -> String {
+        if ids.is_empty() {
+            return "none".to_string();
+        }
+        ids.iter()
+            .map(|id| self.linked(id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// `text` as HTML with every ID linked to its record; undefined IDs are flagged.
+    fn linked(&self, text: &str) -> String {
+        let mut html = String::new();
+        let mut last = 0;
+        for (start, token) in id_tokens(text) {
+            html.push_str(&escape(&text[last..start]));
+            if self.set.defines(token) {
+                html.push_str(&link(token));
+            } else {
+                let _ = write!(
+                    html,
+                    "<span class=\"undefined\" title=\"not defined in {}\">{token}</span>",
+                    IdKind::of(token).map_or("", IdKind::file)
+                );
+            }
+            last = start + token.len();
+        }
+        html.push_str(&escape(&text[last..]));
+        html
+    }
+}
+
+/// Record IDs in `BLUEPRINTS.md` file order.
+fn record_ids(set: &BlueprintSet) -> impl Iterator<Item = &String> {
+    set.requirements
+        .iter()
+        .map(|r| &r.id)
+        .chain(set.spec.clauses.iter().map(|c| &c.id))
+        .chain(set.contracts.iter().map(|c| &c.id))
+        .chain(set.test_vectors.iter().map(|tv| &tv.id))
+        .chain(set.delivery.iter().map(|item| &item.id))
+}
+
+/// Contract heading on 1-based `line` through the line before the next heading.
+fn section(lines: &[&str], line: usize) -> String {
+    let start = line - 1;
+    let end = lines[line..]
+        .iter()
+        .position(|line| line.starts_with("## ") || line.starts_with("### "))
+        .map_or(lines.len(), |offset| line + offset);
+    lines[start..end].join("\n").trim_end().to_string()
+}
+
+fn implemented(tags: &[Tag], id: &str) -> String {
+    let tagged = match IdKind::of(id) {
+        Some(IdKind::Spec) => evidence::missing_evidence(tags, id).is_none(),
+        _ => tags
+            .iter()
+            .any(|tag| tag.ids.iter().any(|tagged| tagged == id)),
+    };
+    if tagged {
+        badge("ok", "implemented")
+    } else {
+        badge("warn", "not implemented")
+    }
+}
+
+fn row(out: &mut String, label: &str, done: usize, total: usize) {
+    let percent = (done * 100).checked_div(total).unwrap_or(0);
+    let _ = writeln!(
+        out,
+        "<tr><td>{label}</td><td>{done}/{total}</td><td><span class=\"bar\"><span style=\"width:{percent}%\"></span></span> {percent}%</td></tr>"
+    );
+}
+
+fn badge(class: &str, label: &str) -> String {
+    format!("<span class=\"badge {class}\">{label}</span>")
+}
+
+fn link(id: &str) -> String {
+    format!("<a class=\"id\" href=\"#{id}\">{id}</a>")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
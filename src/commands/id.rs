@@ -0,0 +1,89 @@
+use anyhow::{Result, anyhow};
+use clap::{Args, Subcommand, ValueEnum};
+
+use super::common::prepare_blueprints;
+use crate::records::{IdKind, ids};
+
+#[derive(Args, Debug)]
+pub struct IdArgs {
+    /// Workspace crate package name.
+    #[arg(
+        long = "crate",
+        value_name = "crate",
+        conflicts_with = "module_path",
+        global = true
+    )]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path", global = true)]
+    pub module_path: Option<String>,
+
+    #[command(subcommand)]
+    pub action: IdAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IdAction {
+    /// Reserve and print the next never-used ID of the given kind.
+    Next(NextArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct NextArgs {
+    /// Kind of ID to allocate.
+    #[arg(value_enum)]
+    pub kind: IdKindArg,
+
+    /// Allocate an `S-###.n` sub-clause under this base spec ID instead.
+    #[arg(long, value_name = "S-###")]
+    pub parent: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum IdKindArg {
+    #[value(alias = "r")]
+    Requirement,
+    #[value(alias = "s")]
+    Spec,
+    #[value(alias = "c")]
+    Contract,
+    #[value(alias = "tv")]
+    TestVector,
+    #[value(alias = "dp")]
+    DeliveryItem,
+}
+
+impl From<IdKindArg> for IdKind {
+    fn from(kind: IdKindArg) -> Self {
+        match kind {
+            IdKindArg::Requirement => Self::Requirement,
+            IdKindArg::Spec => Self::Spec,
+            IdKindArg::Contract => Self::Contract,
+            IdKindArg::TestVector => Self::TestVector,
+            IdKindArg::DeliveryItem => Self::DeliveryItem,
+        }
+    }
+}
+
+pub fn handle(args: &IdArgs) -> Result<()> {
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+
+    match &args.action {
+        IdAction::Next(next) => {
+            // Waits for in-flight `add`/`id next` runs so their IDs count as used.
+            let _lock = ids::lock()?;
+            let id = match (next.kind, next.parent.as_deref()) {
+                (IdKindArg::Spec, Some(parent)) => ids::next_sub_id(blueprints.dir(), parent)?,
+                (_, Some(_)) => {
+                    return Err(anyhow!("--parent is only valid for spec IDs"));
+                }
+                (kind, None) => ids::next_id(blueprints.dir(), kind.into())?,
+            };
+            ids::reserve(blueprints.dir(), &id)?;
+            println!("{id}");
+        }
+    }
+
+    Ok(())
+}
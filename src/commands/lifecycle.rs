@@ -0,0 +1,235 @@
+use anyhow::{Context, Result, anyhow};
+use clap::{Args, Subcommand};
+use std::{fs::OpenOptions, io::Write, path::Path};
+
+use super::common::prepare_blueprints;
+use crate::{
+    logging::log_blueprints,
+    records::{
+        BlueprintSet, IdKind, LIFECYCLE_FILE,
+        lifecycle::{self, LifecycleRecord, Status},
+        read_optional, validate_field,
+    },
+};
+
+#[derive(Args, Debug)]
+pub struct LifecycleArgs {
+    /// Workspace crate package name.
+    #[arg(
+        long = "crate",
+        value_name = "crate",
+        conflicts_with = "module_path",
+        global = true
+    )]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path", global = true)]
+    pub module_path: Option<String>,
+
+    #[command(subcommand)]
+    pub action: LifecycleAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LifecycleAction {
+    /// Append a `STATUS:deprecated` record for an active ID.
+    Deprecate(TransitionArgs),
+    /// Append a `STATUS:removed` record for an active or deprecated ID.
+    Remove(TransitionArgs),
+    /// Append a `STATUS:active` record for a deprecated or removed ID.
+    Reactivate(ReactivateArgs),
+    /// Show the current lifecycle status of one or more IDs.
+    Status(StatusArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct TransitionArgs {
+    /// Blueprint ID (`R-###`, `S-###[.n]`, `TV-###` or `C-###`).
+    pub id: String,
+
+    /// Short reason recorded in the `REASON:` field.
+    #[arg(long)]
+    pub reason: String,
+
+    /// Active ID of the same kind that supersedes this one.
+    #[arg(long = "replace-by", value_name = "id")]
+    pub replace_by: Option<String>,
+
+    /// Semver or date from which the change applies.
+    #[arg(long)]
+    pub effective: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReactivateArgs {
+    /// Blueprint ID (`R-###`, `S-###[.n]`, `TV-###` or `C-###`).
+    pub id: String,
+
+    /// Short reason recorded in the `REASON:` field.
+    #[arg(long)]
+    pub reason: String,
+
+    /// Semver or date from which the change applies.
+    #[arg(long)]
+    pub effective: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct StatusArgs {
+    /// Blueprint IDs to look up.
+    #[arg(required = true)]
+    pub ids: Vec<String>,
+}
+
+pub fn handle(args: &LifecycleArgs) -> Result<()> {
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    let set = BlueprintSet::load(blueprints.dir())?;
+
+    let record = match &args.action {
+        LifecycleAction::Status(status) => {
+            for id in &status.ids {
+                println!("{}", describe_status(&set, id)?);
+            }
+            return Ok(());
+        }
+        LifecycleAction::Deprecate(transition) => {
+            transition_record(&set, Status::Deprecated, transition)?
+        }
+        LifecycleAction::Remove(transition) => {
+            transition_record(&set, Status::Removed, transition)?
+        }
+        LifecycleAction::Reactivate(reactivate) => {
+            validate_target(&set, &reactivate.id)?;
+            let current = set.status_of(&reactivate.id);
+            if current == Status::Active {
+                return Err(anyhow!("{} is already active", reactivate.id));
+            }
+            LifecycleRecord {
+                id: reactivate.id.clone(),
+                status: Status::Active,
+                reason: validate_field("REASON", &reactivate.reason)?,
+                effective: reactivate
+                    .effective
+                    .as_deref()
+                    .map(|value| validate_field("EFFECTIVE", value))
+                    .transpose()?,
+                replace_by: None,
+                line: 0,
+            }
+        }
+    };
+
+    append_record(&blueprints.join(LIFECYCLE_FILE), &record)?;
+    log_blueprints(format!("Appended to {LIFECYCLE_FILE}: {}", record.render()));
+    Ok(())
+}
+
+fn transition_record(
+    set: &BlueprintSet,
+    status: Status,
+    args: &TransitionArgs,
+) -> Result<LifecycleRecord> {
+    validate_target(set, &args.id)?;
+
+    let current = set.status_of(&args.id);
+    let allowed = if status == Status::Deprecated {
+        current == Status::Active
+    } else {
+        current != Status::Removed
+    };
+    if !allowed {
+        return Err(anyhow!(
+            "cannot mark {} as {status}: current status is {current}",
+            args.id
+        ));
+    }
+
+    if let Some(replacement) = args.replace_by.as_deref() {
+        validate_replacement(set, &args.id, replacement)?;
+    }
+
+    Ok(LifecycleRecord {
+        id: args.id.clone(),
+        status,
+        reason: validate_field("REASON", &args.reason)?,
+        effective: args
+            .effective
+            .as_deref()
+            .map(|value| validate_field("EFFECTIVE", value))
+            .transpose()?,
+        replace_by: args.replace_by.clone(),
+        line: 0,
+    })
+}
+
+fn validate_target(set: &BlueprintSet, id: &str) -> Result<()> {
+    if !lifecycle::is_lifecycle_id(id) {
+        return Err(anyhow!(
+            "'{id}' is not a lifecycle ID (expected R-###, S-###[.n], TV-### or C-###)"
+        ));
+    }
+    if !set.defines(id) {
+        let file = IdKind::of(id).map_or("blueprints", IdKind::file);
+        return Err(anyhow!("{id} is not defined in {file}"));
+    }
+    Ok(())
+}
+
+fn validate_replacement(set: &BlueprintSet, id: &str, replacement: &str) -> Result<()> {
+    if replacement == id {
+        return Err(anyhow!("{id} cannot be replaced by itself"));
+    }
+    validate_target(set, replacement)?;
+    if IdKind::of(replacement) != IdKind::of(id) {
+        return Err(anyhow!(
+            "replacement {replacement} must be the same kind of ID as {id}"
+        ));
+    }
+    let status = set.status_of(replacement);
+    if status != Status::Active {
+        return Err(anyhow!(
+            "replacement {replacement} is {status}; it must be active"
+        ));
+    }
+    Ok(())
+}
+
+fn describe_status(set: &BlueprintSet, id: &str) -> Result<String> {
+    if !lifecycle::is_lifecycle_id(id) {
+        return Err(anyhow!("'{id}' is not a lifecycle ID"));
+    }
+
+    let defined = set.location_of(id).map_or_else(
+        || " (not defined in blueprints)".to_string(),
+        |at| format!(" ({at})"),
+    );
+
+    Ok(match lifecycle::latest(&set.lifecycle, id) {
+        Some(record) => format!(
+            "{}{defined} [{LIFECYCLE_FILE}:{}]",
+            record.render(),
+            record.line
+        ),
+        None => format!("{id} | STATUS:active{defined}"),
+    })
+}
+
+fn append_record(path: &Path, record: &LifecycleRecord) -> Result<()> {
+    let existing = read_optional(path)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+
+    let mut line = String::new();
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        line.push('\n');
+    }
+    line.push_str(&record.render());
+    line.push('\n');
+
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("failed to append to {}", path.display()))
+}
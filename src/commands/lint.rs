@@ -0,0 +1,64 @@
+use anyhow::{Result, anyhow};
+use clap::Args;
+
+use super::common::prepare_blueprints;
+use crate::{
+    findings::{self, Finding, Format},
+    logging::log_blueprints,
+    records::{
+        BlueprintSet, CONTRACTS_FILE, REQUIREMENTS_FILE, SPEC_FILE, TEST_VECTORS_FILE,
+        read_optional, schema,
+    },
+    tags,
+};
+
+#[derive(Args, Debug)]
+pub struct LintArgs {
+    /// Workspace crate package name.
+    #[arg(long = "crate", value_name = "crate", conflicts_with = "module_path")]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path")]
+    pub module_path: Option<String>,
+
+    /// Output format; `json` and `sarif` are written to stdout.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+pub fn handle(args: &LintArgs) -> Result<()> {
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    let read = |file| read_optional(&blueprints.join(file));
+    let mut findings: Vec<Finding> = schema::check_all(
+        &read(REQUIREMENTS_FILE)?,
+        &read(SPEC_FILE)?,
+        &read(CONTRACTS_FILE)?,
+        &read(TEST_VECTORS_FILE)?,
+    )
+    .into_iter()
+    .map(|violation| Finding::of_violation(blueprints.dir(), violation))
+    .collect();
+
+    let set = BlueprintSet::load(blueprints.dir())?;
+    let tags = tags::scan_crate(blueprints.crate_dir())?;
+    findings.extend(findings::orphans(
+        blueprints.dir(),
+        blueprints.crate_dir(),
+        &set,
+        tags.iter(),
+    ));
+
+    findings::emit(&findings, args.format)?;
+    let errors = findings::errors(&findings);
+    if errors > 0 {
+        return Err(anyhow!("{errors} blueprint lint error(s)"));
+    }
+    if args.format == Format::Text {
+        log_blueprints(format!(
+            "Blueprints lint clean ({} warning(s))",
+            findings.len()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,461 @@
+//! `blueprints lsp`: a stdio language server for the blueprint Markdown files.
+//!
+//! Documents the editor has open are read from its buffers; everything else is
+//! read from disk on every request, so the server never works from a stale index.
+
+use anyhow::Result;
+use clap::Args;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, Diagnostic,
+    DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, GotoDefinitionParams, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind, NumberOrString, OneOf, Position,
+    PublishDiagnosticsParams, Range, ReferenceParams, ServerCapabilities,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, References, Request as _},
+};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use super::common::{BlueprintsContext, prepare_blueprints};
+use crate::{
+    logging::log_blueprints,
+    records::{
+        CONTRACTS_FILE, DELIVERY_PLAN_FILE, FIELD_SEPARATOR, IdKind, LIFECYCLE_FILE,
+        REQUIREMENTS_FILE, SPEC_FILE, TEST_VECTORS_FILE, id_tokens, read_optional, schema,
+    },
+    tags,
+};
+
+/// Blueprint files searched for references, in `BLUEPRINTS.md` order.
+const FILES: [&str; 6] = [
+    REQUIREMENTS_FILE,
+    SPEC_FILE,
+    CONTRACTS_FILE,
+    TEST_VECTORS_FILE,
+    DELIVERY_PLAN_FILE,
+    LIFECYCLE_FILE,
+];
+
+/// Files covered by the schema rules and therefore by diagnostics.
+const SCHEMA_FILES: [&str; 4] = [
+    REQUIREMENTS_FILE,
+    SPEC_FILE,
+    CONTRACTS_FILE,
+    TEST_VECTORS_FILE,
+];
+
+/// IDs offered after `Refs:` in delivery plan items.
+const REFS_KINDS: [IdKind; 4] = [
+    IdKind::Requirement,
+    IdKind::Spec,
+    IdKind::Contract,
+    IdKind::TestVector,
+];
+
+#[derive(Args, Debug)]
+pub struct LspArgs {
+    /// Workspace crate package name.
+    #[arg(long = "crate", value_name = "crate", conflicts_with = "module_path")]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path")]
+    pub module_path: Option<String>,
+}
+
+pub fn handle(args: &LspArgs) -> Result<()> {
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string(), ",".to_string()]),
+            ..CompletionOptions::default()
+        }),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+    log_blueprints(format!(
+        "Language server ready for {}",
+        blueprints.dir().display()
+    ));
+
+    let mut server = Server {
+        connection: &connection,
+        blueprints,
+        documents: HashMap::new(),
+    };
+    server.publish_diagnostics()?;
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+                server.request(request)?;
+            }
+            Message::Notification(notification) => server.notification(notification)?,
+            Message::Response(_) => {}
+        }
+    }
+
+    drop(server);
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    blueprints: BlueprintsContext,
+    /// Contents of the documents the editor has open, which may be unsaved.
+    documents: HashMap<PathBuf, String>,
+}
+
+impl Server<'_> {
+    fn request(&self, request: Request) -> Result<()> {
+        let response = match self.respond(&request.method, request.params) {
+            Ok(Some(result)) => Response::new_ok(request.id, result),
+            Ok(None) => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request {}", request.method),
+            ),
+            Err(error) => Response::new_err(
+                request.id,
+                ErrorCode::InvalidParams as i32,
+                error.to_string(),
+            ),
+        };
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    /// The result for `method`, or `None` if the server does not support it.
+    fn respond(&self, method: &str, params: Value) -> Result<Option<Value>> {
+        let result = match method {
+            GotoDefinition::METHOD => {
+                let params: GotoDefinitionParams = serde_json::from_value(params)?;
+                serde_json::to_value(self.definition(&params.text_document_position_params))?
+            }
+            References::METHOD => {
+                let params: ReferenceParams = serde_json::from_value(params)?;
+                serde_json::to_value(self.references(&params))?
+            }
+            HoverRequest::METHOD => {
+                let params: HoverParams = serde_json::from_value(params)?;
+                serde_json::to_value(self.hover(&params.text_document_position_params))?
+            }
+            Completion::METHOD => {
+                let params: CompletionParams = serde_json::from_value(params)?;
+                serde_json::to_value(self.completion(&params.text_document_position))?
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(result))
+    }
+
+    fn notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if let Some(path) = path_of(&params.text_document.uri) {
+                    self.documents.insert(path, params.text_document.text);
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                // Full sync: the last change carries the whole document.
+                if let (Some(path), Some(change)) = (
+                    path_of(&params.text_document.uri),
+                    params.content_changes.into_iter().last(),
+                ) {
+                    self.documents.insert(path, change.text);
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if let Some(path) = path_of(&params.text_document.uri) {
+                    self.documents.remove(&path);
+                }
+            }
+            DidSaveTextDocument::METHOD => {}
+            _ => return Ok(()),
+        }
+        self.publish_diagnostics()
+    }
+
+    /// Schema violations for every schema file; files without any get an empty list,
+    /// which clears diagnostics an earlier edit produced.
+    fn publish_diagnostics(&self) -> Result<()> {
+        let texts: Vec<String> = SCHEMA_FILES
+            .iter()
+            .map(|file| self.file_text(file))
+            .collect();
+        let violations = schema::check_all(&texts[0], &texts[1], &texts[2], &texts[3]);
+
+        for (file, text) in SCHEMA_FILES.iter().zip(&texts) {
+            let Some(uri) = uri_of(&self.blueprints.join(file)) else {
+                continue;
+            };
+            let lines: Vec<&str> = text.lines().collect();
+            let diagnostics = violations
+                .iter()
+                .filter(|violation| violation.file == *file)
+                .map(|violation| {
+                    let index = violation.line.saturating_sub(1);
+                    let line = lines.get(index).copied().unwrap_or_default();
+                    Diagnostic {
+                        range: range(index, line, 0, line.len()),
+                        severity: Some(DiagnosticSeverity::ERROR),
+                        code: Some(NumberOrString::String(violation.rule.id().to_string())),
+                        source: Some("blueprints".to_string()),
+                        message: violation.message.clone(),
+                        ..Diagnostic::default()
+                    }
+                })
+                .collect();
+            let params = PublishDiagnosticsParams {
+                uri,
+                diagnostics,
+                version: None,
+            };
+            self.connection
+                .sender
+                .send(Notification::new(PublishDiagnostics::METHOD.to_string(), params).into())?;
+        }
+        Ok(())
+    }
+
+    fn definition(&self, position: &TextDocumentPositionParams) -> Option<Location> {
+        let id = self.id_at(position)?;
+        self.definition_of(&id).map(|(location, _)| location)
+    }
+
+    /// Every mention of the ID under the cursor in the blueprint files and in the
+    /// crate's traceability tags.
+    fn references(&self, params: &ReferenceParams) -> Option<Vec<Location>> {
+        let id = self.id_at(&params.text_document_position)?;
+        let declaration = self.definition_of(&id).map(|(location, _)| location);
+
+        let mut locations = Vec::new();
+        for file in FILES {
+            let path = self.blueprints.join(file);
+            locations.extend(occurrences(&path, &self.text(&path), &id));
+        }
+
+        for path in tags::rust_files(self.blueprints.crate_dir()).unwrap_or_default() {
+            let text = self.text(&path);
+            let lines: Vec<&str> = text.lines().collect();
+            for tag in tags::scan_source(&path, &text) {
+                if !tag.ids.contains(&id) {
+                    continue;
+                }
+                let index = tag.line - 1;
+                let Some(uri) = uri_of(&path) else {
+                    continue;
+                };
+                locations.extend(
+                    id_tokens(lines.get(index).copied().unwrap_or_default())
+                        .into_iter()
+                        .filter(|(_, token)| *token == id)
+                        .map(|(start, token)| Location {
+                            uri: uri.clone(),
+                            range: range(index, lines[index], start, start + token.len()),
+                        }),
+                );
+            }
+        }
+
+        if !params.context.include_declaration {
+            locations.retain(|location| Some(location) != declaration.as_ref());
+        }
+        locations.dedup();
+        Some(locations)
+    }
+
+    fn hover(&self, position: &TextDocumentPositionParams) -> Option<Hover> {
+        let id = self.id_at(position)?;
+        let (location, record) = self.definition_of(&id)?;
+        let file = IdKind::of(&id)?.file();
+        let line = location.range.start.line + 1;
+
+        let value = if IdKind::of(&id) == Some(IdKind::Contract) {
+            // Contract sections are Markdown already, code blocks included.
+            format!("`{file}:{line}`\n\n{record}")
+        } else {
+            format!("`{file}:{line}`\n\n```text\n{record}\n```")
+        };
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        })
+    }
+
+    /// Existing IDs for the `R:` field of spec clauses and test vectors, and for the
+    /// `Refs:` list of delivery plan items.
+    fn completion(&self, position: &TextDocumentPositionParams) -> Option<Vec<CompletionItem>> {
+        let path = path_of(&position.text_document.uri)?;
+        let text = self.text(&path);
+        let line = text.lines().nth(position.position.line as usize)?;
+        let prefix = &line[..byte_offset(line, position.position.character)];
+
+        let (kinds, field): (&[IdKind], &str) = match prefix.rsplit_once("Refs:") {
+            Some((_, refs)) if !refs.contains(';') => (&REFS_KINDS, refs),
+            _ => {
+                let field = prefix.rsplit(FIELD_SEPARATOR).next()?.trim_start();
+                (&[IdKind::Requirement], field.strip_prefix("R:")?)
+            }
+        };
+        let listed: Vec<&str> = field.split(',').map(str::trim).collect();
+
+        let mut items = Vec::new();
+        for kind in kinds {
+            let text = self.file_text(kind.file());
+            for record in text.lines().map(str::trim_end) {
+                let Some(id) = kind.record_id(record) else {
+                    continue;
+                };
+                if listed.contains(&id) {
+                    continue;
+                }
+                items.push(CompletionItem {
+                    label: id.to_string(),
+                    kind: Some(CompletionItemKind::REFERENCE),
+                    detail: Some(summary(record, id).to_string()),
+                    ..CompletionItem::default()
+                });
+            }
+        }
+        Some(items)
+    }
+
+    /// Where `id` is defined, and the text of its record.
+    fn definition_of(&self, id: &str) -> Option<(Location, String)> {
+        let kind = IdKind::of(id)?;
+        let path = self.blueprints.join(kind.file());
+        let text = self.text(&path);
+        let lines: Vec<&str> = text.lines().collect();
+        let index = lines
+            .iter()
+            .position(|line| kind.record_id(line.trim_end()) == Some(id))?;
+        let start = lines[index].find(id)?;
+
+        let record = if kind == IdKind::Contract {
+            let end = lines[index + 1..]
+                .iter()
+                .position(|line| line.starts_with("## ") || line.starts_with("### "))
+                .map_or(lines.len(), |offset| index + 1 + offset);
+            lines[index..end].join("\n").trim_end().to_string()
+        } else {
+            lines[index].trim_end().to_string()
+        };
+
+        let location = Location {
+            uri: uri_of(&path)?,
+            range: range(index, lines[index], start, start + id.len()),
+        };
+        Some((location, record))
+    }
+
+    fn id_at(&self, position: &TextDocumentPositionParams) -> Option<String> {
+        let path = path_of(&position.text_document.uri)?;
+        let text = self.text(&path);
+        let line = text.lines().nth(position.position.line as usize)?;
+        let offset = byte_offset(line, position.position.character);
+        id_tokens(line)
+            .into_iter()
+            .find(|(start, token)| (*start..=start + token.len()).contains(&offset))
+            .map(|(_, token)| token.to_string())
+    }
+
+    fn file_text(&self, file: &str) -> String {
+        self.text(&self.blueprints.join(file))
+    }
+
+    /// The editor's copy of `path` if it is open, otherwise the file on disk.
+    fn text(&self, path: &Path) -> String {
+        self.documents
+            .get(path)
+            .cloned()
+            .unwrap_or_else(|| read_optional(path).unwrap_or_default())
+    }
+}
+
+fn occurrences(path: &Path, text: &str, id: &str) -> Vec<Location> {
+    let Some(uri) = uri_of(path) else {
+        return Vec::new();
+    };
+    let mut locations = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        for (start, token) in id_tokens(line) {
+            if token == id {
+                locations.push(Location {
+                    uri: uri.clone(),
+                    range: range(index, line, start, start + token.len()),
+                });
+            }
+        }
+    }
+    locations
+}
+
+/// A record line without its leading ID and separator, for completion details.
+fn summary<'a>(record: &'a str, id: &str) -> &'a str {
+    record
+        .split_once(id)
+        .map_or(record, |(_, rest)| rest)
+        .trim_start_matches([' ', '|', '-', '—'])
+}
+
+/// Byte offset of an LSP (UTF-16) column within `line`.
+fn byte_offset(line: &str, character: u32) -> usize {
+    let mut units = 0;
+    for (idx, c) in line.char_indices() {
+        if units >= character as usize {
+            return idx;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+/// The LSP range for bytes `start..end` of `line`, which is line `index` of its file.
+fn range(index: usize, line: &str, start: usize, end: usize) -> Range {
+    let line_number = u32::try_from(index).unwrap_or(u32::MAX);
+    let column =
+        |byte: usize| u32::try_from(line[..byte].encode_utf16().count()).unwrap_or(u32::MAX);
+    Range::new(
+        Position::new(line_number, column(start)),
+        Position::new(line_number, column(end)),
+    )
+}
+
+fn path_of(uri: &Url) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+    Some(path.canonicalize().unwrap_or(path))
+}
+
+fn uri_of(path: &Path) -> Option<Url> {
+    Url::from_file_path(path).ok()
+}
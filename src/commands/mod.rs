@@ -1,7 +1,20 @@
+pub mod add;
+pub mod bootstrap;
+pub mod check_history;
 pub mod common;
 pub mod contracts;
 pub mod delivery;
+pub mod export;
+pub mod id;
 pub mod implement;
+pub mod lifecycle;
+pub mod lint;
+pub mod lsp;
+pub mod progress;
 pub mod requirements;
+pub mod scaffold;
+pub mod serve;
 pub mod specs;
 pub mod tests;
+pub mod verify_plan;
+pub mod watch;
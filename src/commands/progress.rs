@@ -0,0 +1,208 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use std::{path::Path, process::Command};
+
+use super::common::prepare_blueprints;
+use crate::records::{
+    BlueprintSet, DELIVERY_PLAN_FILE, IdKind,
+    delivery::{self, DeliveryItem},
+    lifecycle::Status,
+};
+
+/// Width of the completion bars in the `--history` chart.
+const BAR_WIDTH: usize = 30;
+
+/// Items before the first `## Milestone:` heading.
+const NO_MILESTONE: &str = "(no milestone)";
+
+#[derive(Args, Debug)]
+pub struct ProgressArgs {
+    /// Workspace crate package name.
+    #[arg(long = "crate", value_name = "crate", conflicts_with = "module_path")]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path")]
+    pub module_path: Option<String>,
+
+    /// Chart overall completion for every day the delivery plan changed in git history.
+    #[arg(long)]
+    pub history: bool,
+}
+
+pub fn handle(args: &ProgressArgs) -> Result<()> {
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    let set = BlueprintSet::load(blueprints.dir())?;
+
+    if set.delivery.is_empty() {
+        println!("No DP-### checklist items in {DELIVERY_PLAN_FILE}");
+    } else {
+        print_milestones(&set.delivery);
+        print_blocked(&set);
+    }
+
+    if args.history {
+        print_history(blueprints.dir(), &set.delivery)?;
+    }
+    Ok(())
+}
+
+/// Milestones in plan order, each with its (done, total) counts.
+pub(crate) fn milestones(items: &[DeliveryItem]) -> Vec<(&str, usize, usize)> {
+    let mut milestones: Vec<(&str, usize, usize)> = Vec::new();
+    for item in items {
+        let title = item.milestone.as_deref().unwrap_or(NO_MILESTONE);
+        let idx = milestones
+            .iter()
+            .position(|(known, _, _)| *known == title)
+            .unwrap_or_else(|| {
+                milestones.push((title, 0, 0));
+                milestones.len() - 1
+            });
+        milestones[idx].2 += 1;
+        if item.done {
+            milestones[idx].1 += 1;
+        }
+    }
+    milestones
+}
+
+fn print_milestones(items: &[DeliveryItem]) {
+    let milestones = milestones(items);
+    let width = milestones
+        .iter()
+        .map(|(title, _, _)| title.len())
+        .max()
+        .unwrap_or(0)
+        .max("Total".len());
+    for (title, done, total) in &milestones {
+        println!("{title:<width$}  {}", ratio(*done, *total));
+    }
+    let (done, total) = completion(items);
+    println!("{:<width$}  {}", "Total", ratio(done, total));
+}
+
+/// Open items whose referenced spec clause or test vector is missing or removed,
+/// with the reason for each reference.
+pub(crate) fn blocked(set: &BlueprintSet) -> Vec<(&DeliveryItem, Vec<String>)> {
+    set.delivery
+        .iter()
+        .filter(|item| !item.done)
+        .filter_map(|item| {
+            let reasons: Vec<String> = item
+                .refs
+                .iter()
+                .filter(|id| matches!(IdKind::of(id), Some(IdKind::Spec | IdKind::TestVector)))
+                .filter_map(|id| {
+                    if !set.defines(id) {
+                        Some(format!("{id} missing"))
+                    } else if set.status_of(id) == Status::Removed {
+                        Some(format!("{id} removed"))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            (!reasons.is_empty()).then_some((item, reasons))
+        })
+        .collect()
+}
+
+fn print_blocked(set: &BlueprintSet) {
+    let blocked = blocked(set);
+    if blocked.is_empty() {
+        return;
+    }
+    println!();
+    println!("Blocked:");
+    for (item, reasons) in blocked {
+        println!(
+            "{DELIVERY_PLAN_FILE}:{}: {} ({})",
+            item.line,
+            item.text,
+            reasons.join(", ")
+        );
+    }
+}
+
+/// One chart row per day the delivery plan was committed (last commit of the day),
+/// plus the working tree when it differs from the newest commit.
+fn print_history(blueprints_dir: &Path, current: &[DeliveryItem]) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(blueprints_dir)
+        .args([
+            "log",
+            "--reverse",
+            "--format=%h %cs",
+            "--",
+            DELIVERY_PLAN_FILE,
+        ])
+        .output()
+        .context("failed to run git log")?;
+
+    // (label `<date> <hash>`, date, completion)
+    let mut rows: Vec<(String, String, (usize, usize))> = Vec::new();
+    if output.status.success() {
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let Some((hash, date)) = line.split_once(' ') else {
+                continue;
+            };
+            let items = delivery::parse(&show(blueprints_dir, hash));
+            if rows.last().is_some_and(|(_, last, _)| last == date) {
+                rows.pop();
+            }
+            rows.push((
+                format!("{date} {hash}"),
+                date.to_string(),
+                completion(&items),
+            ));
+        }
+    }
+
+    let now = completion(current);
+    if rows.last().is_none_or(|(_, _, last)| *last != now) {
+        rows.push(("working tree".to_string(), String::new(), now));
+    }
+
+    println!();
+    println!("History:");
+    let width = rows
+        .iter()
+        .map(|(label, _, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    for (label, _, (done, total)) in rows {
+        let filled = (done * BAR_WIDTH).checked_div(total).unwrap_or(0);
+        println!(
+            "{label:<width$} [{}{}] {}",
+            "#".repeat(filled),
+            "-".repeat(BAR_WIDTH - filled),
+            ratio(done, total)
+        );
+    }
+    Ok(())
+}
+
+/// The delivery plan as of `rev`; empty when it did not exist yet.
+fn show(blueprints_dir: &Path, rev: &str) -> String {
+    Command::new("git")
+        .arg("-C")
+        .arg(blueprints_dir)
+        .arg("show")
+        .arg(format!("{rev}:./{DELIVERY_PLAN_FILE}"))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default()
+}
+
+pub(crate) fn completion(items: &[DeliveryItem]) -> (usize, usize) {
+    (items.iter().filter(|item| item.done).count(), items.len())
+}
+
+fn ratio(done: usize, total: usize) -> String {
+    let percent = (done * 100).checked_div(total).unwrap_or(0);
+    format!("{done:>3}/{total:<3} {percent:>3}%")
+}
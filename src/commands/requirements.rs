@@ -1,10 +1,15 @@
 use anyhow::{Context, Result};
 use clap::Args;
-use std::process::{Command, Stdio};
+use std::{
+    path::PathBuf,
+    process::{Command, Stdio},
+};
 
 use super::common::{
-    WorkflowMode, list_macos_sound_names, play_notification_chime_with, prepare_blueprints,
+    WorkflowMode, ensure_schema_valid, list_macos_sound_names, play_notification_chime_with,
+    prepare_blueprints, read_brief, run_non_interactive,
 };
+use crate::records::{REQUIREMENTS_FILE, read_optional, schema};
 
 const DESIGN_PROMPT: &str = include_str!("../prompts/requirements/DESIGN.md");
 const UPDATE_PROMPT: &str = include_str!("../prompts/requirements/UPDATE.md");
@@ -30,6 +35,14 @@ pub struct RequirementsArgs {
     /// List available macOS system sounds and exit
     #[arg(long)]
     pub list_sounds: bool,
+
+    /// Run headlessly via `codex exec`, using this file as the human's answers
+    #[arg(long, value_name = "file")]
+    pub brief: Option<PathBuf>,
+
+    /// Run headlessly via `codex exec` instead of opening an interactive session
+    #[arg(long)]
+    pub non_interactive: bool,
 }
 
 pub fn handle(args: &RequirementsArgs) -> Result<()> {
@@ -40,6 +53,7 @@ pub fn handle(args: &RequirementsArgs) -> Result<()> {
         return Ok(());
     }
     let sound = args.sound.as_deref();
+    let brief = args.brief.as_deref().map(read_brief).transpose()?;
 
     let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
     let template = match args.mode {
@@ -48,6 +62,16 @@ pub fn handle(args: &RequirementsArgs) -> Result<()> {
     };
     let prompt = blueprints.apply(template);
 
+    if args.non_interactive || brief.is_some() {
+        run_non_interactive(&prompt, brief.as_deref())?;
+
+        let path = blueprints.join(REQUIREMENTS_FILE);
+        let content = read_optional(&path)?;
+        ensure_schema_valid(&path, &schema::check_requirements(&content))?;
+        play_notification_chime_with(sound);
+        return Ok(());
+    }
+
     let status = Command::new("codex")
         .args([
             "--model",
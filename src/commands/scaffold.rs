@@ -0,0 +1,362 @@
+use anyhow::{Context, Result, anyhow};
+use clap::{Args, Subcommand};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::common::prepare_blueprints;
+use crate::{
+    logging::log_blueprints,
+    records::{BlueprintSet, lifecycle::Status, test_vectors::TestVector},
+    tags::{self, Tag, TagKind},
+};
+
+/// Integration stubs for `L:I` vectors are collected in this file under `tests/`.
+const INTEGRATION_FILE: &str = "tests/test_vectors.rs";
+/// Name of the `#[cfg(test)]` module that receives unit stubs.
+const UNIT_MODULE: &str = "tv_scaffold";
+/// Maximum number of words taken from the clause title for a test name.
+const NAME_WORDS: usize = 6;
+
+#[derive(Args, Debug)]
+pub struct ScaffoldArgs {
+    /// Workspace crate package name.
+    #[arg(
+        long = "crate",
+        value_name = "crate",
+        conflicts_with = "module_path",
+        global = true
+    )]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path", global = true)]
+    pub module_path: Option<String>,
+
+    #[command(subcommand)]
+    pub target: ScaffoldTarget,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ScaffoldTarget {
+    /// Emit one `#[test]` stub per test vector that has no `@tv` tagged test yet.
+    Tests(TestsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct TestsArgs {
+    /// Print where each stub would go without writing any file.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+pub fn handle(args: &ScaffoldArgs) -> Result<()> {
+    let ctx = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    match &args.target {
+        ScaffoldTarget::Tests(tests) => scaffold_tests(ctx.dir(), ctx.crate_dir(), tests),
+    }
+}
+
+fn scaffold_tests(blueprints_dir: &Path, crate_dir: &Path, args: &TestsArgs) -> Result<()> {
+    let set = BlueprintSet::load(blueprints_dir)?;
+    if set.test_vectors.is_empty() {
+        return Err(anyhow!(
+            "no test vectors found in {}",
+            blueprints_dir
+                .join(crate::records::TEST_VECTORS_FILE)
+                .display()
+        ));
+    }
+
+    let tags = tags::scan_crate(crate_dir)?;
+    let tested: BTreeSet<&str> = tags
+        .iter()
+        .filter(|tag| tag.kind == TagKind::TestVector)
+        .flat_map(|tag| tag.ids.iter().map(String::as_str))
+        .collect();
+
+    let mut plan: BTreeMap<PathBuf, Vec<&TestVector>> = BTreeMap::new();
+    let mut skipped = 0;
+    for tv in &set.test_vectors {
+        if tested.contains(tv.id.as_str()) || set.status_of(&tv.id) == Status::Removed {
+            if args.dry_run {
+                log_blueprints(format!("Skipping {}: {}", tv.id, skip_reason(tv, &tags)));
+            }
+            skipped += 1;
+            continue;
+        }
+        let target = target_file(tv, &tags, crate_dir)?;
+        plan.entry(target).or_default().push(tv);
+    }
+
+    if plan.is_empty() {
+        log_blueprints(format!(
+            "All {} test vectors already have tests; nothing to scaffold.",
+            set.test_vectors.len()
+        ));
+        return Ok(());
+    }
+
+    let mut written = 0;
+    for (file, vectors) in &plan {
+        let is_integration = file == Path::new(INTEGRATION_FILE);
+        for tv in vectors {
+            let verb = if args.dry_run {
+                "Would scaffold"
+            } else {
+                "Scaffolding"
+            };
+            log_blueprints(format!("{verb} {} in {}", tv.id, file.display()));
+        }
+        if args.dry_run {
+            continue;
+        }
+
+        let indent = if is_integration { "" } else { "    " };
+        let stubs = vectors
+            .iter()
+            .map(|tv| render_stub(tv, &set, indent))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let path = crate_dir.join(file);
+        if is_integration {
+            append_integration(&path, &stubs)?;
+        } else {
+            append_unit(&path, &stubs)?;
+        }
+        written += vectors.len();
+    }
+
+    if plan.values().flatten().any(|tv| tv.has_level('P')) && !has_proptest(crate_dir) {
+        log_blueprints(
+            "Property stubs use proptest; add `proptest` to [dev-dependencies] in Cargo.toml.",
+        );
+    }
+
+    if !args.dry_run {
+        log_blueprints(format!(
+            "Scaffolded {written} test stub(s); {skipped} vector(s) already had tests or were removed."
+        ));
+    }
+    Ok(())
+}
+
+fn skip_reason(tv: &TestVector, tags: &[Tag]) -> String {
+    let Some(tag) = tags
+        .iter()
+        .find(|tag| tag.mentions(TagKind::TestVector, &tv.id))
+    else {
+        return "removed in lifecycle ledger".to_string();
+    };
+    match &tag.item {
+        Some(item) => format!(
+            "already tested by `{}` at {}:{}",
+            item.name,
+            tag.file.display(),
+            item.line
+        ),
+        None => format!("already tested at {}:{}", tag.file.display(), tag.line),
+    }
+}
+
+/// `tests/` for integration vectors, otherwise the source file implementing the clause.
+fn target_file(tv: &TestVector, tags: &[Tag], crate_dir: &Path) -> Result<PathBuf> {
+    if tv.has_level('I') {
+        return Ok(PathBuf::from(INTEGRATION_FILE));
+    }
+
+    let implementing = |kind: TagKind, ids: &[String]| {
+        ids.iter().find_map(|id| {
+            tags.iter()
+                .filter(|tag| tag.file.starts_with("src"))
+                .filter(|tag| tag.item.as_ref().is_some_and(|item| !item.is_test))
+                .find(|tag| tag.mentions(kind, id))
+                .map(|tag| tag.file.clone())
+        })
+    };
+
+    if let Some(file) = implementing(TagKind::Spec, &tv.specs)
+        .or_else(|| implementing(TagKind::Impl, &tv.requirements))
+    {
+        return Ok(file);
+    }
+
+    ["src/lib.rs", "src/main.rs"]
+        .into_iter()
+        .map(PathBuf::from)
+        .find(|file| crate_dir.join(file).is_file())
+        .ok_or_else(|| {
+            anyhow!(
+                "{} needs a unit test but {} has neither src/lib.rs nor src/main.rs",
+                tv.id,
+                crate_dir.display()
+            )
+        })
+}
+
+fn render_stub(tv: &TestVector, set: &BlueprintSet, indent: &str) -> String {
+    let title = tv
+        .specs
+        .iter()
+        .find_map(|id| set.spec.clause(id).and_then(|clause| clause.title()))
+        .unwrap_or(&tv.when);
+    let name = format!("{}_{}", tv.id.to_lowercase().replace('-', "_"), slug(title));
+    let property = tv.has_level('P');
+
+    let mut stub = String::new();
+    let mut inner = indent.to_string();
+    if property {
+        let _ = writeln!(stub, "{indent}proptest::proptest! {{");
+        inner.push_str("    ");
+    }
+
+    let _ = writeln!(stub, "{inner}/// @covers({})", tv.requirements.join(","));
+    let _ = writeln!(stub, "{inner}/// @tv({})", tv.id);
+    let _ = writeln!(stub, "{inner}/// @s({})", tv.specs.join(","));
+    let _ = writeln!(stub, "{inner}#[test]");
+    if property {
+        let _ = writeln!(
+            stub,
+            "{inner}fn {name}(input in proptest::prelude::any::<u64>()) {{"
+        );
+    } else {
+        let _ = writeln!(stub, "{inner}fn {name}() {{");
+    }
+
+    let _ = writeln!(stub, "{inner}    // GIVEN: {}", tv.given);
+    let _ = writeln!(stub, "{inner}    // WHEN: {}", tv.when);
+    let _ = writeln!(stub, "{inner}    // THEN: {}", tv.then);
+    for (key, value) in &tv.extra {
+        let _ = writeln!(stub, "{inner}    // {key}: {value}");
+    }
+    if property {
+        let _ = writeln!(stub, "{inner}    let _ = input;");
+        // A diverging `todo!()` would leave proptest's generated `Ok(())` unreachable.
+        let _ = writeln!(
+            stub,
+            "{inner}    proptest::prop_assert!(false, \"{} not implemented\");",
+            tv.id
+        );
+    } else {
+        let _ = writeln!(stub, "{inner}    todo!(\"{}\");", tv.id);
+    }
+    let _ = writeln!(stub, "{inner}}}");
+
+    if property {
+        let _ = writeln!(stub, "{indent}}}");
+    }
+    stub
+}
+
+fn slug(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(NAME_WORDS)
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn append_integration(path: &Path, stubs: &str) -> Result<()> {
+    let mut content = if path.exists() {
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+
+    if !content.is_empty() {
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push('\n');
+    }
+    content.push_str(stubs);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Add `stubs` to the file's `tv_scaffold` test module, creating the module if needed.
+fn append_unit(path: &Path, stubs: &str) -> Result<()> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let header = format!("mod {UNIT_MODULE} {{");
+
+    let updated = if let Some(start) = lines.iter().position(|line| line.trim() == header) {
+        let close = closing_line(&lines, start).ok_or_else(|| {
+            anyhow!(
+                "could not find the end of `mod {UNIT_MODULE}` in {}",
+                path.display()
+            )
+        })?;
+        let mut out = lines[..close].join("\n");
+        out.push_str("\n\n");
+        out.push_str(stubs);
+        out.push_str(&lines[close..].join("\n"));
+        out.push('\n');
+        out
+    } else {
+        let mut out = content.trim_end().to_string();
+        let _ = write!(out, "\n\n#[cfg(test)]\n{header}\n{stubs}}}\n");
+        out
+    };
+
+    fs::write(path, updated).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Index of the line holding the brace that closes the block opened on `start`.
+fn closing_line(lines: &[&str], start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if in_string {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => in_string = true,
+                '/' if chars.peek() == Some(&'/') => break,
+                '\'' => {
+                    // Skip char literals such as '{' or '\n'; lifetimes fall through.
+                    let rest: String = chars.clone().take(3).collect();
+                    if rest.starts_with('\\') {
+                        chars.by_ref().take_while(|&ch| ch != '\'').for_each(drop);
+                    } else if rest.chars().nth(1) == Some('\'') {
+                        chars.nth(1);
+                    }
+                }
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.checked_sub(1)?;
+                    if depth == 0 {
+                        return Some(idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+fn has_proptest(crate_dir: &Path) -> bool {
+    fs::read_to_string(crate_dir.join("Cargo.toml"))
+        .is_ok_and(|manifest| manifest.contains("proptest"))
+}
@@ -0,0 +1,932 @@
+//! `blueprints serve`: a local HTTP/JSON-RPC server for editors and tools.
+//!
+//! Workflow runs are child processes of this binary (`blueprints implement ...`), so
+//! they behave exactly as on the command line; they log JSON records (`--log-events`)
+//! that become events.
+//! Every REST route is also a JSON-RPC method on `POST /rpc`.
+
+use anyhow::{Context, anyhow};
+use clap::Args;
+use serde_json::{Map, Value, json};
+use std::{
+    collections::BTreeMap,
+    env,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::SocketAddr,
+    process::{Child, Command, Stdio},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant},
+};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::{
+    common::{BlueprintsContext, prepare_blueprints},
+    progress, watch,
+};
+use crate::{
+    logging::{self, log_blueprints, log_error},
+    records::{
+        BlueprintSet, CONTRACTS_FILE, REQUIREMENTS_FILE, SPEC_FILE, TEST_VECTORS_FILE,
+        read_optional, schema,
+    },
+    tags::{self, Tag},
+};
+
+/// Workflows that can be started as runs.
+const WORKFLOWS: [&str; 3] = ["implement", "delivery", "tests"];
+
+/// How a pass-through flag takes its value.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum FlagValue {
+    None,
+    /// `--flag value` or `--flag=value`.
+    Required,
+    /// Only `--flag=value`, as for `--summarize`.
+    OptionalInline,
+}
+
+/// Flags a client may pass to a run: workflow (`""` for all), flag, value.
+/// Anything else is rejected so clients cannot redirect logs or attach a terminal UI.
+const PASS_THROUGH: [(&str, &str, FlagValue); 10] = [
+    ("", "--summarize", FlagValue::OptionalInline),
+    ("", "--summarize-interval", FlagValue::Required),
+    ("implement", "--coverage", FlagValue::None),
+    ("implement", "--coverage-threshold", FlagValue::Required),
+    ("implement", "--capture-regressions", FlagValue::None),
+    ("implement", "--milestone", FlagValue::Required),
+    ("implement", "--items", FlagValue::Required),
+    ("implement", "--one-at-a-time", FlagValue::None),
+    ("tests", "--mutants", FlagValue::None),
+    ("tests", "--mutation-threshold", FlagValue::Required),
+];
+
+/// JSON-RPC methods; the REST routes map onto the same names.
+const METHODS: [&str; 9] = [
+    "runs.list",
+    "runs.start",
+    "runs.get",
+    "runs.stop",
+    "runs.resume",
+    "runs.events",
+    "lint",
+    "trace",
+    "progress",
+];
+
+/// Comment sent on idle event streams so dropped clients are noticed.
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// Loopback address to listen on.
+    #[arg(long, value_name = "addr", default_value = "127.0.0.1:7878")]
+    pub listen: SocketAddr,
+}
+
+pub fn handle(args: &ServeArgs) -> anyhow::Result<()> {
+    if !args.listen.ip().is_loopback() {
+        return Err(anyhow!(
+            "--listen must be a loopback address; the server starts agent runs for any client"
+        ));
+    }
+    let server = Server::http(args.listen)
+        .map_err(|error| anyhow!("failed to listen on {}: {error}", args.listen))?;
+    let shared = Arc::new(Shared {
+        runs: Mutex::new(Runs::default()),
+        changed: Condvar::new(),
+        exe: env::current_exe().context("failed to locate the blueprints executable")?,
+        listen: args.listen,
+    });
+    log_blueprints(format!("Serving on http://{}", args.listen));
+
+    for request in server.incoming_requests() {
+        let shared = Arc::clone(&shared);
+        // Event streams stay open for the whole run, so every request gets a thread.
+        thread::spawn(move || {
+            if let Err(error) = serve(&shared, request) {
+                log_error(format!("failed to answer request: {error}"));
+            }
+        });
+    }
+    Ok(())
+}
+
+/// An error answered with an HTTP status (and the matching JSON-RPC code).
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: 400,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: 404,
+            message: message.into(),
+        }
+    }
+
+    fn conflict(message: impl Into<String>) -> Self {
+        Self {
+            status: 409,
+            message: message.into(),
+        }
+    }
+
+    fn rpc_code(&self) -> i64 {
+        match self.status {
+            400 => -32602,
+            404 => -32001,
+            409 => -32002,
+            _ => -32603,
+        }
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(error: anyhow::Error) -> Self {
+        Self {
+            status: 500,
+            message: format!("{error:#}"),
+        }
+    }
+}
+
+type ApiResult = Result<Value, ApiError>;
+
+struct Shared {
+    runs: Mutex<Runs>,
+    /// Signalled whenever a run gains an event or changes state.
+    changed: Condvar,
+    exe: std::path::PathBuf,
+    listen: SocketAddr,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Runs> {
+        self.runs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[derive(Default)]
+struct Runs {
+    next_id: u64,
+    runs: BTreeMap<u64, Run>,
+}
+
+impl Runs {
+    fn get(&self, id: u64) -> Result<&Run, ApiError> {
+        self.runs
+            .get(&id)
+            .ok_or_else(|| ApiError::not_found(format!("no run {id}")))
+    }
+}
+
+/// The `--crate`/`--module` pair a run or query targets.
+#[derive(Clone, PartialEq, Eq)]
+struct Target {
+    crate_name: Option<String>,
+    module_path: Option<String>,
+}
+
+impl Target {
+    fn from_params(params: &Value) -> Result<Self, ApiError> {
+        let field = |key: &str| params.get(key).and_then(Value::as_str).map(str::to_string);
+        let target = Self {
+            crate_name: field("crate"),
+            module_path: field("module"),
+        };
+        match (&target.crate_name, &target.module_path) {
+            (None, None) => Err(ApiError::bad_request("`crate` or `module` is required")),
+            (Some(_), Some(_)) => Err(ApiError::bad_request(
+                "`crate` and `module` are mutually exclusive",
+            )),
+            _ => Ok(target),
+        }
+    }
+
+    fn flags(&self) -> Vec<String> {
+        match (&self.crate_name, &self.module_path) {
+            (Some(name), _) => vec!["--crate".to_string(), name.clone()],
+            (None, Some(path)) => vec!["--module".to_string(), path.clone()],
+            (None, None) => Vec::new(),
+        }
+    }
+
+    fn blueprints(&self) -> anyhow::Result<BlueprintsContext> {
+        prepare_blueprints(self.crate_name.as_deref(), self.module_path.as_deref())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum RunState {
+    Running,
+    /// Stop requested; the process has not exited yet.
+    Stopping,
+    Succeeded,
+    Failed(Option<i32>),
+    Stopped,
+}
+
+impl RunState {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Stopping => "stopping",
+            Self::Succeeded => "succeeded",
+            Self::Failed(_) => "failed",
+            Self::Stopped => "stopped",
+        }
+    }
+
+    fn is_live(self) -> bool {
+        matches!(self, Self::Running | Self::Stopping)
+    }
+}
+
+struct Run {
+    workflow: String,
+    target: Target,
+    /// Extra command-line flags passed through to the workflow.
+    args: Vec<String>,
+    resumed_from: Option<u64>,
+    state: RunState,
+    started: Instant,
+    /// Events in order; an event's `seq` is its index plus one.
+    events: Vec<Value>,
+    /// Taken by the monitor thread once the output pipes close.
+    child: Option<Child>,
+}
+
+impl Run {
+    fn summary(&self, id: u64) -> Value {
+        let exit_code = match self.state {
+            RunState::Failed(code) => code,
+            RunState::Succeeded => Some(0),
+            _ => None,
+        };
+        json!({
+            "id": id,
+            "workflow": self.workflow,
+            "crate": self.target.crate_name,
+            "module": self.target.module_path,
+            "args": self.args,
+            "state": self.state.as_str(),
+            "exit_code": exit_code,
+            "resumed_from": self.resumed_from,
+            "events": self.events.len(),
+            "elapsed_ms": elapsed_ms(self.started),
+        })
+    }
+
+    fn push(&mut self, mut event: Value) {
+        event["seq"] = json!(self.events.len() + 1);
+        event["elapsed_ms"] = json!(elapsed_ms(self.started));
+        self.events.push(event);
+    }
+}
+
+fn serve(shared: &Arc<Shared>, mut request: Request) -> io::Result<()> {
+    if let Err(error) = check_caller(&request, shared.listen) {
+        return respond(request, 403, &json!({ "error": error }));
+    }
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let mut params = query_params(query);
+
+    let method = match (request.method(), segments.as_slice()) {
+        (Method::Post, ["rpc"]) => {
+            let body = read_body(&mut request);
+            return respond(request, 200, &rpc(shared, body));
+        }
+        (Method::Get, ["runs", id, "stream"]) => {
+            let after = params.get("after").and_then(as_number).unwrap_or(0);
+            return match id.parse() {
+                Ok(id) => stream(shared, request, id, after),
+                Err(_) => respond(request, 404, &json!({ "error": "no such run" })),
+            };
+        }
+        (Method::Get, ["runs"]) => "runs.list",
+        (Method::Post, ["runs"]) => {
+            match read_body(&mut request) {
+                Ok(Value::Object(body)) => params.extend(body),
+                Ok(_) => params.clear(),
+                Err(error) => return respond(request, 400, &json!({ "error": error.message })),
+            }
+            "runs.start"
+        }
+        (Method::Get, ["runs", _]) => "runs.get",
+        (Method::Post, ["runs", _, "stop"]) => "runs.stop",
+        (Method::Post, ["runs", _, "resume"]) => "runs.resume",
+        (Method::Get, ["runs", _, "events"]) => "runs.events",
+        (Method::Get, ["lint"]) => "lint",
+        (Method::Get, ["trace"]) => "trace",
+        (Method::Get, ["progress"]) => "progress",
+        _ => return respond(request, 404, &json!({ "error": "no such endpoint" })),
+    };
+    if let ["runs", id, ..] = segments.as_slice() {
+        params.insert("id".to_string(), Value::String((*id).to_string()));
+    }
+
+    match call(shared, method, &Value::Object(params)) {
+        Ok(result) => respond(request, 200, &result),
+        Err(error) => respond(request, error.status, &json!({ "error": error.message })),
+    }
+}
+
+/// Loopback binding alone does not stop web pages from posting to the server:
+/// browsers send `Origin` on cross-site requests, and DNS rebinding shows up as a
+/// foreign `Host`. Bodies must be declared JSON, which a page cannot do without a
+/// CORS preflight this server never answers.
+fn check_caller(request: &Request, listen: SocketAddr) -> Result<(), String> {
+    let header = |name: &'static str| {
+        request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv(name))
+            .map(|header| header.value.as_str().trim())
+    };
+    if header("Origin").is_some() {
+        return Err("browser requests (with an Origin header) are not accepted".to_string());
+    }
+    let local_host = format!("localhost:{}", listen.port());
+    match header("Host") {
+        Some(host) if host == listen.to_string() || host.eq_ignore_ascii_case(&local_host) => {}
+        _ => return Err(format!("Host must be {listen} or {local_host}")),
+    }
+    let is_json = header("Content-Type").is_some_and(|value| {
+        value
+            .split(';')
+            .next()
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+    });
+    if *request.method() == Method::Post && !is_json {
+        return Err("POST requests must have Content-Type: application/json".to_string());
+    }
+    Ok(())
+}
+
+/// One JSON-RPC 2.0 request; notifications (no `id`) are answered like requests.
+fn rpc(shared: &Arc<Shared>, body: Result<Value, ApiError>) -> Value {
+    let body = match body {
+        Ok(body) => body,
+        Err(error) => return rpc_error(&Value::Null, -32700, &error.message),
+    };
+    let id = body.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = body.get("method").and_then(Value::as_str) else {
+        return rpc_error(&id, -32600, "missing method");
+    };
+    if !METHODS.contains(&method) {
+        return rpc_error(&id, -32601, &format!("unknown method {method}"));
+    }
+    let params = body.get("params").cloned().unwrap_or_else(|| json!({}));
+    match call(shared, method, &params) {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => rpc_error(&id, error.rpc_code(), &error.message),
+    }
+}
+
+fn rpc_error(id: &Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn call(shared: &Arc<Shared>, method: &str, params: &Value) -> ApiResult {
+    let id = || {
+        params
+            .get("id")
+            .and_then(as_number)
+            .ok_or_else(|| ApiError::bad_request("`id` must be a run number"))
+    };
+
+    match method {
+        "runs.list" => {
+            let runs = shared.lock();
+            Ok(Value::Array(
+                runs.runs.iter().map(|(id, run)| run.summary(*id)).collect(),
+            ))
+        }
+        "runs.start" => {
+            let workflow = params
+                .get("workflow")
+                .and_then(Value::as_str)
+                .filter(|workflow| WORKFLOWS.contains(workflow))
+                .ok_or_else(|| {
+                    ApiError::bad_request(format!("`workflow` must be one of {WORKFLOWS:?}"))
+                })?;
+            let args = match params.get("args") {
+                None | Some(Value::Null) => Vec::new(),
+                Some(Value::Array(args)) => args
+                    .iter()
+                    .map(|arg| arg.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| ApiError::bad_request("`args` must be strings"))?,
+                Some(_) => return Err(ApiError::bad_request("`args` must be an array")),
+            };
+            check_args(workflow, &args)?;
+            let target = Target::from_params(params)?;
+            start(shared, workflow.to_string(), target, args, None)
+        }
+        "runs.get" => {
+            let id = id()?;
+            Ok(shared.lock().get(id)?.summary(id))
+        }
+        "runs.stop" => stop(shared, id()?),
+        "runs.resume" => {
+            let id = id()?;
+            let (workflow, target, args) = {
+                let runs = shared.lock();
+                let run = runs.get(id)?;
+                if run.state.is_live() {
+                    return Err(ApiError::conflict(format!("run {id} is still running")));
+                }
+                (run.workflow.clone(), run.target.clone(), run.args.clone())
+            };
+            start(shared, workflow, target, args, Some(id))
+        }
+        "runs.events" => {
+            let id = id()?;
+            let after = params.get("after").and_then(as_number).unwrap_or(0);
+            let runs = shared.lock();
+            let run = runs.get(id)?;
+            let start = usize::try_from(after)
+                .unwrap_or(usize::MAX)
+                .min(run.events.len());
+            Ok(json!({
+                "state": run.state.as_str(),
+                "events": run.events[start..],
+            }))
+        }
+        "lint" => lint(&Target::from_params(params)?.blueprints()?),
+        "trace" => trace(&Target::from_params(params)?.blueprints()?),
+        "progress" => progress(&Target::from_params(params)?.blueprints()?),
+        _ => Err(ApiError::not_found(format!("unknown method {method}"))),
+    }
+}
+
+/// Reject flags outside [`PASS_THROUGH`] and values that look like flags.
+fn check_args(workflow: &str, args: &[String]) -> Result<(), ApiError> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value)),
+            None => (arg.as_str(), None),
+        };
+        let Some((_, _, value)) = PASS_THROUGH
+            .iter()
+            .find(|(only, name, _)| (only.is_empty() || *only == workflow) && *name == flag)
+        else {
+            return Err(ApiError::bad_request(format!(
+                "`{flag}` cannot be passed to {workflow} runs"
+            )));
+        };
+        let value_ok = match value {
+            FlagValue::None => inline.is_none(),
+            FlagValue::OptionalInline => true,
+            FlagValue::Required => {
+                inline.is_some() || args.next().is_some_and(|next| !next.starts_with('-'))
+            }
+        };
+        if !value_ok {
+            return Err(ApiError::bad_request(format!("invalid value for `{flag}`")));
+        }
+    }
+    Ok(())
+}
+
+/// Spawn `blueprints <workflow>` for `target`; one live run per target at a time.
+fn start(
+    shared: &Arc<Shared>,
+    workflow: String,
+    target: Target,
+    args: Vec<String>,
+    resumed_from: Option<u64>,
+) -> ApiResult {
+    let mut runs = shared.lock();
+    if let Some((id, _)) = runs
+        .runs
+        .iter()
+        .find(|(_, run)| run.target == target && run.state.is_live())
+    {
+        return Err(ApiError::conflict(format!(
+            "run {id} is already working on this crate"
+        )));
+    }
+
+    let mut child = Command::new(&shared.exe)
+        .arg(&workflow)
+        .arg("--log-events")
+        .args(target.flags())
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to start blueprints {workflow}"))?;
+    let stdout = child.stdout.take().context("run stdout pipe unavailable")?;
+    let stderr = child.stderr.take().context("run stderr pipe unavailable")?;
+
+    runs.next_id += 1;
+    let id = runs.next_id;
+    let mut run = Run {
+        workflow,
+        target,
+        args,
+        resumed_from,
+        state: RunState::Running,
+        started: Instant::now(),
+        events: Vec::new(),
+        child: Some(child),
+    };
+    run.push(json!({ "type": "state", "state": "running" }));
+    let summary = run.summary(id);
+    runs.runs.insert(id, run);
+    drop(runs);
+
+    let shared = Arc::clone(shared);
+    thread::spawn(move || monitor(&shared, id, stdout, stderr));
+    Ok(summary)
+}
+
+/// Forward a run's output as events, then record how it ended.
+fn monitor(shared: &Arc<Shared>, id: u64, stdout: impl Read, stderr: impl Read + Send + 'static) {
+    let errors = {
+        let shared = Arc::clone(shared);
+        thread::spawn(move || forward(&shared, id, "stderr", stderr))
+    };
+    forward(shared, id, "stdout", stdout);
+    let _ = errors.join();
+
+    let child = shared
+        .lock()
+        .runs
+        .get_mut(&id)
+        .and_then(|run| run.child.take());
+    let status = child.map(|mut child| child.wait());
+
+    let mut runs = shared.lock();
+    if let Some(run) = runs.runs.get_mut(&id) {
+        run.state = match (run.state, status) {
+            (RunState::Stopping, _) => RunState::Stopped,
+            (_, Some(Ok(status))) if status.success() => RunState::Succeeded,
+            (_, Some(Ok(status))) => RunState::Failed(status.code()),
+            _ => RunState::Failed(None),
+        };
+        let state = run.state;
+        run.push(json!({ "type": "state", "state": state.as_str() }));
+    }
+    drop(runs);
+    shared.changed.notify_all();
+}
+
+/// Turn each output line into a `log` event. Runs log through `--log-events`, so
+/// their log lines arrive as JSON records (label, level, message, role, iteration, ...);
+/// verbatim Codex output has no label.
+fn forward(shared: &Shared, id: u64, stream: &str, output: impl Read) {
+    let mut reader = BufReader::new(output);
+    let mut buffer = Vec::new();
+    while reader.read_until(b'\n', &mut buffer).unwrap_or(0) > 0 {
+        let line = String::from_utf8_lossy(&buffer).trim_end().to_string();
+        buffer.clear();
+        let mut event = match serde_json::from_str::<Value>(&line) {
+            Ok(Value::Object(record))
+                if record.contains_key("label") && record.contains_key("level") =>
+            {
+                record
+            }
+            _ => {
+                let mut raw = Map::new();
+                raw.insert("label".to_string(), Value::Null);
+                raw.insert("message".to_string(), json!(logging::strip_ansi(&line)));
+                raw
+            }
+        };
+        event.insert("type".to_string(), json!("log"));
+        event.insert("stream".to_string(), json!(stream));
+
+        if let Some(run) = shared.lock().runs.get_mut(&id) {
+            run.push(Value::Object(event));
+        }
+        shared.changed.notify_all();
+    }
+}
+
+fn stop(shared: &Arc<Shared>, id: u64) -> ApiResult {
+    let mut runs = shared.lock();
+    let run = runs
+        .runs
+        .get_mut(&id)
+        .ok_or_else(|| ApiError::not_found(format!("no run {id}")))?;
+    if run.state == RunState::Running
+        && let Some(child) = run.child.as_mut()
+    {
+        terminate(child).context("failed to stop the run")?;
+        run.state = RunState::Stopping;
+        run.push(json!({ "type": "state", "state": "stopping" }));
+    }
+    let summary = run.summary(id);
+    drop(runs);
+    shared.changed.notify_all();
+    Ok(summary)
+}
+
+/// Send SIGTERM to the run and the Codex or cargo processes it started.
+#[cfg(unix)]
+fn terminate(child: &mut Child) -> io::Result<()> {
+    let pid = child.id().to_string();
+    let _ = Command::new("pkill").args(["-TERM", "-P", &pid]).status();
+    let status = Command::new("kill").args(["-TERM", &pid]).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        child.kill()
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(child: &mut Child) -> io::Result<()> {
+    child.kill()
+}
+
+/// Server-sent events for run `id`, starting after event `after`; the stream ends
+/// with the run's final `state` event.
+fn stream(shared: &Shared, request: Request, id: u64, after: u64) -> io::Result<()> {
+    if !shared.lock().runs.contains_key(&id) {
+        return respond(request, 404, &json!({ "error": format!("no run {id}") }));
+    }
+    let mut next = usize::try_from(after).unwrap_or(usize::MAX);
+
+    // tiny_http buffers chunked bodies; write the response by hand so each event flushes.
+    let mut writer = request.into_writer();
+    write!(
+        writer,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n"
+    )?;
+    writer.flush()?;
+
+    loop {
+        let (events, live) = {
+            let mut runs = shared.lock();
+            loop {
+                let Some(run) = runs.runs.get(&id) else {
+                    return Ok(());
+                };
+                let pending = run.events.get(next..).unwrap_or_default();
+                if !pending.is_empty() || !run.state.is_live() {
+                    break (pending.to_vec(), run.state.is_live());
+                }
+                let (guard, timeout) = shared
+                    .changed
+                    .wait_timeout(runs, KEEP_ALIVE)
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                runs = guard;
+                if timeout.timed_out() {
+                    drop(runs);
+                    write!(writer, ": keep-alive\n\n")?;
+                    writer.flush()?;
+                    runs = shared.lock();
+                }
+            }
+        };
+
+        for event in &events {
+            write!(writer, "id: {}\ndata: {event}\n\n", event["seq"])?;
+        }
+        writer.flush()?;
+        next += events.len();
+        if !live {
+            return Ok(());
+        }
+    }
+}
+
+fn lint(blueprints: &BlueprintsContext) -> ApiResult {
+    let read = |file| read_optional(&blueprints.join(file));
+    let violations = schema::check_all(
+        &read(REQUIREMENTS_FILE)?,
+        &read(SPEC_FILE)?,
+        &read(CONTRACTS_FILE)?,
+        &read(TEST_VECTORS_FILE)?,
+    );
+    Ok(json!({
+        "violations": violations
+            .iter()
+            .map(|violation| json!({
+                "file": violation.file,
+                "line": violation.line,
+                "rule": violation.rule.id(),
+                "message": violation.message,
+            }))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+/// Every record with the records that reference it and the tagged code, plus the
+/// orphans `watch` reports.
+fn trace(blueprints: &BlueprintsContext) -> ApiResult {
+    let set = BlueprintSet::load(blueprints.dir())?;
+    let tags = tags::scan_crate(blueprints.crate_dir())?;
+    let code = |id: &str| code_locations(&tags, id);
+    let planned = |id: &String| -> Vec<&str> {
+        set.delivery
+            .iter()
+            .filter(|item| item.refs.contains(id))
+            .map(|item| item.id.as_str())
+            .collect()
+    };
+
+    let requirements: Vec<Value> = set
+        .requirements
+        .iter()
+        .map(|requirement| {
+            json!({
+                "id": requirement.id,
+                "line": requirement.line,
+                "status": set.status_of(&requirement.id).as_str(),
+                "specs": set.spec.clauses.iter()
+                    .filter(|clause| clause.requirements.contains(&requirement.id))
+                    .map(|clause| clause.id.as_str())
+                    .collect::<Vec<_>>(),
+                "test_vectors": set.test_vectors.iter()
+                    .filter(|tv| tv.requirements.contains(&requirement.id))
+                    .map(|tv| tv.id.as_str())
+                    .collect::<Vec<_>>(),
+                "code": code(&requirement.id),
+            })
+        })
+        .collect();
+    let specs: Vec<Value> = set
+        .spec
+        .clauses
+        .iter()
+        .map(|clause| {
+            json!({
+                "id": clause.id,
+                "line": clause.line,
+                "status": set.status_of(&clause.id).as_str(),
+                "title": clause.title(),
+                "requirements": clause.requirements,
+                "test_vectors": set.test_vectors.iter()
+                    .filter(|tv| tv.specs.contains(&clause.id))
+                    .map(|tv| tv.id.as_str())
+                    .collect::<Vec<_>>(),
+                "delivery_items": planned(&clause.id),
+                "code": code(&clause.id),
+            })
+        })
+        .collect();
+    let contracts: Vec<Value> = set
+        .contracts
+        .iter()
+        .map(|contract| {
+            json!({
+                "id": contract.id,
+                "line": contract.line,
+                "status": set.status_of(&contract.id).as_str(),
+                "code": code(&contract.id),
+            })
+        })
+        .collect();
+    let test_vectors: Vec<Value> = set
+        .test_vectors
+        .iter()
+        .map(|tv| {
+            json!({
+                "id": tv.id,
+                "line": tv.line,
+                "status": set.status_of(&tv.id).as_str(),
+                "requirements": tv.requirements,
+                "specs": tv.specs,
+                "delivery_items": planned(&tv.id),
+                "code": code(&tv.id),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "requirements": requirements,
+        "specs": specs,
+        "contracts": contracts,
+        "test_vectors": test_vectors,
+        "orphans": watch::orphans(blueprints, &set, tags.iter()),
+    }))
+}
+
+/// Tags naming `id`, with paths relative to the crate directory.
+fn code_locations(tags: &[Tag], id: &str) -> Vec<Value> {
+    tags.iter()
+        .filter(|tag| tag.ids.iter().any(|tagged| tagged == id))
+        .map(|tag| {
+            json!({
+                "file": tag.file.display().to_string(),
+                "line": tag.line,
+                "tag": tag.kind.name(),
+                "item": tag.item.as_ref().map(|item| item.name.as_str()),
+            })
+        })
+        .collect()
+}
+
+fn progress(blueprints: &BlueprintsContext) -> ApiResult {
+    let set = BlueprintSet::load(blueprints.dir())?;
+    let (done, total) = progress::completion(&set.delivery);
+    Ok(json!({
+        "done": done,
+        "total": total,
+        "milestones": progress::milestones(&set.delivery)
+            .into_iter()
+            .map(|(title, done, total)| json!({ "title": title, "done": done, "total": total }))
+            .collect::<Vec<_>>(),
+        "blocked": progress::blocked(&set)
+            .into_iter()
+            .map(|(item, reasons)| json!({
+                "id": item.id,
+                "line": item.line,
+                "text": item.text,
+                "reasons": reasons,
+            }))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+fn respond(request: Request, status: u16, body: &Value) -> io::Result<()> {
+    let header = Header::from_bytes("Content-Type", "application/json")
+        .map_err(|()| io::Error::other("invalid header"))?;
+    request.respond(
+        Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(header),
+    )
+}
+
+/// The request body as JSON; an empty body is an empty object.
+fn read_body(request: &mut Request) -> Result<Value, ApiError> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|error| ApiError::bad_request(format!("failed to read body: {error}")))?;
+    if body.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    serde_json::from_str(&body)
+        .map_err(|error| ApiError::bad_request(format!("invalid JSON body: {error}")))
+}
+
+/// `a=1&b=x%2Fy` as string values.
+fn query_params(query: &str) -> Map<String, Value> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), Value::String(decode(value)))
+        })
+        .collect()
+}
+
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = (bytes[idx] == b'%')
+            .then(|| text.get(idx + 1..idx + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[idx]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                idx += 2;
+            }
+            (None, b'+') => decoded.push(b' '),
+            (None, byte) => decoded.push(byte),
+        }
+        idx += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// A number given either as JSON or as a query-string value.
+fn as_number(value: &Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|text| text.parse().ok()))
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
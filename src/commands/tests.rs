@@ -2,11 +2,19 @@ use anyhow::{Result, anyhow};
 use clap::Args;
 use std::thread;
 
-use super::common::{
-    Tokens, WorkflowConfig, describe_exit, list_macos_sound_names, play_notification_chime_with,
-    prepare_blueprints, run_codex,
+use super::{
+    check_history,
+    common::{
+        BlueprintsContext, Tokens, WorkflowConfig, describe_exit, list_macos_sound_names,
+        play_notification_chime_with, prepare_blueprints,
+    },
+};
+use crate::{
+    logging::log_blueprints,
+    mutants::{self, MutationReport},
+    policy::{Guard, Policy},
+    records::TEST_VECTORS_FILE,
 };
-use crate::logging::log_blueprints;
 
 const BUILDER_PROMPT_TEMPLATE: &str = include_str!("../prompts/tests/BUILDER.md");
 const REVIEWER_PROMPT_TEMPLATE: &str = include_str!("../prompts/tests/REVIEWER.md");
@@ -28,6 +36,19 @@ pub struct TestsArgs {
     /// List available macOS system sounds and exit
     #[arg(long)]
     pub list_sounds: bool,
+
+    /// After reviewer sign-off, require a mutation score via `cargo mutants`
+    #[arg(long)]
+    pub mutants: bool,
+
+    /// Minimum mutation score (percent of viable mutants caught) with `--mutants`
+    #[arg(
+        long,
+        value_name = "percent",
+        default_value_t = 80.0,
+        requires = "mutants"
+    )]
+    pub mutation_threshold: f64,
 }
 
 pub fn handle(args: &TestsArgs) -> Result<()> {
@@ -37,6 +58,9 @@ pub fn handle(args: &TestsArgs) -> Result<()> {
         }
         return Ok(());
     }
+    if !(0.0..=100.0).contains(&args.mutation_threshold) {
+        return Err(anyhow!("--mutation-threshold must be between 0 and 100"));
+    }
     let sound = args.sound.as_deref();
 
     let tokens = Tokens::new();
@@ -45,8 +69,15 @@ pub fn handle(args: &TestsArgs) -> Result<()> {
     let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
     let reviewer_prompt = blueprints.apply(tokens.apply(REVIEWER_PROMPT_TEMPLATE));
     let builder_template = blueprints.apply(tokens.apply(BUILDER_PROMPT_TEMPLATE));
+    let mut reviewer_guard = Guard::new(Policy::read_only("reviewer"));
+    let mut builder_guard = Guard::new(Policy::allow_only(
+        "builder",
+        [blueprints.join(TEST_VECTORS_FILE)],
+    ));
 
     let mut review_cycle = 0usize;
+    // The one mutation run, once it has happened.
+    let mut mutation: Option<MutationReport> = None;
     loop {
         if review_cycle >= config.max_reviewer_iters {
             return Err(anyhow!(
@@ -56,7 +87,7 @@ pub fn handle(args: &TestsArgs) -> Result<()> {
         }
         review_cycle += 1;
 
-        let reviewer = run_codex(
+        let reviewer = reviewer_guard.run(
             &[
                 "exec",
                 "--model",
@@ -83,20 +114,42 @@ pub fn handle(args: &TestsArgs) -> Result<()> {
             return Err(anyhow!("reviewer reported {}", tokens.error));
         }
 
-        if reviewer_trimmed == tokens.completed {
-            log_blueprints("Reviewer sign-off detected");
-            play_notification_chime_with(sound);
-            return Ok(());
-        }
-
-        let plan = extract_plan(&reviewer.stdout)
-            .ok_or_else(|| anyhow!("reviewer did not emit a parseable Implementation Plan"))?;
+        let (plan, surviving_mutants) = if reviewer_trimmed == tokens.completed {
+            // The builder may only edit TV lines, which cannot kill mutants: survivors get
+            // one TV-refinement pass, then are reported instead of re-running cargo mutants.
+            if let Some(report) = mutation.as_ref().filter(|report| !report.work.is_empty()) {
+                return Err(anyhow!(
+                    "{}; surviving mutants need Rust tests (`blueprints scaffold tests`, then `blueprints implement`):\n{}",
+                    report.summary,
+                    report.work.join("\n")
+                ));
+            }
+            let work = match &mutation {
+                None if args.mutants => {
+                    let report = run_mutation_stage(&blueprints, args.mutation_threshold)?;
+                    let work = report.work.clone();
+                    mutation = Some(report);
+                    work
+                }
+                _ => Vec::new(),
+            };
+            if work.is_empty() {
+                log_blueprints("Reviewer sign-off detected");
+                play_notification_chime_with(sound);
+                return Ok(());
+            }
+            (String::new(), work.join("\n"))
+        } else {
+            let plan = extract_plan(&reviewer.stdout)
+                .ok_or_else(|| anyhow!("reviewer did not emit a parseable Implementation Plan"))?;
 
-        if plan.trim().is_empty() {
-            return Err(anyhow!(
-                "reviewer emitted empty plan between ---PLAN START--- and ---PLAN END---"
-            ));
-        }
+            if plan.trim().is_empty() {
+                return Err(anyhow!(
+                    "reviewer emitted empty plan between ---PLAN START--- and ---PLAN END---"
+                ));
+            }
+            (plan, "none".to_string())
+        };
 
         let mut builder_iter = 0usize;
         let mut builder_completed = false;
@@ -104,8 +157,10 @@ pub fn handle(args: &TestsArgs) -> Result<()> {
         while builder_iter < config.max_builder_iters {
             builder_iter += 1;
 
-            let builder_prompt = builder_template.replace("${IMPLEMENTATION_PLAN}", &plan);
-            let builder = run_codex(
+            let builder_prompt = builder_template
+                .replace("${IMPLEMENTATION_PLAN}", &plan)
+                .replace("${SURVIVING_MUTANTS}", &surviving_mutants);
+            let builder = builder_guard.run(
                 &[
                     "exec",
                     "--model",
@@ -129,6 +184,7 @@ pub fn handle(args: &TestsArgs) -> Result<()> {
             if builder_trimmed == tokens.error {
                 return Err(anyhow!("builder reported {}", tokens.error));
             }
+            check_history::enforce(&blueprints)?;
 
             if builder_trimmed == tokens.completed {
                 builder_completed = true;
@@ -156,6 +212,22 @@ pub fn handle(args: &TestsArgs) -> Result<()> {
     }
 }
 
+fn run_mutation_stage(blueprints: &BlueprintsContext, threshold: f64) -> Result<MutationReport> {
+    log_blueprints("Running cargo mutants for the mutation score");
+    let report = mutants::run(
+        blueprints.module(),
+        blueprints.dir(),
+        blueprints.crate_dir(),
+        threshold,
+    )?;
+
+    log_blueprints(&report.summary);
+    for item in &report.work {
+        log_blueprints(item);
+    }
+    Ok(report)
+}
+
 fn extract_plan(output: &str) -> Option<String> {
     let mut in_plan = false;
     let mut lines = Vec::new();
@@ -0,0 +1,41 @@
+use anyhow::{Result, anyhow};
+use clap::Args;
+
+use super::common::prepare_blueprints;
+use crate::{
+    evidence,
+    logging::{log_blueprints, log_error},
+    records::DELIVERY_PLAN_FILE,
+};
+
+#[derive(Args, Debug)]
+pub struct VerifyPlanArgs {
+    /// Workspace crate package name.
+    #[arg(long = "crate", value_name = "crate", conflicts_with = "module_path")]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path")]
+    pub module_path: Option<String>,
+}
+
+pub fn handle(args: &VerifyPlanArgs) -> Result<()> {
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    let path = blueprints.join(DELIVERY_PLAN_FILE);
+
+    let (checked, unsupported) = evidence::verify(blueprints.dir(), blueprints.crate_dir())?;
+    if unsupported.is_empty() {
+        log_blueprints(format!(
+            "All {checked} checked Delivery Plan item(s) have tagged tests and code."
+        ));
+        return Ok(());
+    }
+
+    for item in &unsupported {
+        log_error(format!("{}:{item}", path.display()));
+    }
+    Err(anyhow!(
+        "{} of {checked} checked item(s) in {DELIVERY_PLAN_FILE} lack evidence",
+        unsupported.len()
+    ))
+}
@@ -0,0 +1,301 @@
+use anyhow::{Context, Result, anyhow};
+use clap::Args;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use super::common::{BlueprintsContext, prepare_blueprints};
+use crate::{
+    findings,
+    logging::log_blueprints,
+    records::{
+        BlueprintSet, CONTRACTS_FILE, DELIVERY_PLAN_FILE, REQUIREMENTS_FILE, SPEC_FILE,
+        TEST_VECTORS_FILE, read_optional, schema,
+    },
+    tags::{self, Tag},
+};
+
+/// Stage, its output file, and the blueprint files it is derived from.
+/// `None` as output stands for the crate sources.
+const STAGES: [(&str, Option<&str>, &[&str]); 4] = [
+    ("specs", Some(SPEC_FILE), &[REQUIREMENTS_FILE]),
+    (
+        "tests",
+        Some(TEST_VECTORS_FILE),
+        &[REQUIREMENTS_FILE, SPEC_FILE, CONTRACTS_FILE],
+    ),
+    (
+        "delivery",
+        Some(DELIVERY_PLAN_FILE),
+        &[SPEC_FILE, CONTRACTS_FILE, TEST_VECTORS_FILE],
+    ),
+    ("implement", None, &[DELIVERY_PLAN_FILE]),
+];
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    /// Workspace crate package name.
+    #[arg(long = "crate", value_name = "crate", conflicts_with = "module_path")]
+    pub crate_name: Option<String>,
+
+    /// Optional module path within the workspace (e.g. `crates/crate_b/module_a`).
+    #[arg(long = "module", value_name = "module-path")]
+    pub module_path: Option<String>,
+
+    /// Milliseconds between polls of the watched files.
+    #[arg(long, value_name = "ms", default_value_t = 500)]
+    pub interval: u64,
+
+    /// Milliseconds the files must stay unchanged before a pass runs.
+    #[arg(long, value_name = "ms", default_value_t = 300)]
+    pub debounce: u64,
+}
+
+pub fn handle(args: &WatchArgs) -> Result<()> {
+    if args.interval == 0 {
+        return Err(anyhow!("--interval must be greater than 0"));
+    }
+    let blueprints = prepare_blueprints(args.crate_name.as_deref(), args.module_path.as_deref())?;
+    let interval = Duration::from_millis(args.interval);
+    let debounce = Duration::from_millis(args.debounce);
+
+    log_blueprints(format!(
+        "Watching {} and the sources of {} (polling every {}ms; Ctrl-C to stop)",
+        blueprints.dir().display(),
+        blueprints.crate_dir().display(),
+        args.interval
+    ));
+
+    let mut stamps = stamps(&blueprints)?;
+    let mut state = State::default();
+    state.refresh(&blueprints, &stamps.keys().cloned().collect::<Vec<_>>())?;
+    state.report(&blueprints, &stamps);
+
+    loop {
+        thread::sleep(interval);
+        let mut current = self::stamps(&blueprints)?;
+        if current == stamps {
+            continue;
+        }
+
+        // Editors often write a file several times per save; wait for quiet.
+        loop {
+            thread::sleep(debounce);
+            let settled = self::stamps(&blueprints)?;
+            if settled == current {
+                break;
+            }
+            current = settled;
+        }
+
+        let changed = changed_paths(&stamps, &current);
+        stamps = current;
+        println!();
+        log_blueprints(format!(
+            "Changed: {}",
+            changed
+                .iter()
+                .map(|path| display(&blueprints, path))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        state.refresh(&blueprints, &changed)?;
+        state.report(&blueprints, &stamps);
+    }
+}
+
+/// Modification time and length of a watched file.
+type Stamp = (SystemTime, u64);
+
+/// Parsed blueprints plus per-file tag scans, refreshed only for changed files.
+#[derive(Default)]
+struct State {
+    set: Option<BlueprintSet>,
+    lint: Vec<String>,
+    tags: BTreeMap<PathBuf, Vec<Tag>>,
+    /// Orphans reported by the previous pass; only new ones are printed again.
+    orphans: Option<BTreeSet<String>>,
+}
+
+impl State {
+    fn refresh(&mut self, blueprints: &BlueprintsContext, changed: &[PathBuf]) -> Result<()> {
+        if self.set.is_none()
+            || changed
+                .iter()
+                .any(|path| path.starts_with(blueprints.dir()))
+        {
+            let read = |file| read_optional(&blueprints.join(file));
+            self.lint = schema::check_all(
+                &read(REQUIREMENTS_FILE)?,
+                &read(SPEC_FILE)?,
+                &read(CONTRACTS_FILE)?,
+                &read(TEST_VECTORS_FILE)?,
+            )
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+            self.set = Some(BlueprintSet::load(blueprints.dir())?);
+        }
+
+        for path in changed
+            .iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        {
+            let relative = path
+                .strip_prefix(blueprints.crate_dir())
+                .unwrap_or(path)
+                .to_path_buf();
+            if !path.exists() {
+                self.tags.remove(&relative);
+                continue;
+            }
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let tags = tags::scan_source(&relative, &content);
+            self.tags.insert(relative, tags);
+        }
+        Ok(())
+    }
+
+    fn report(&mut self, blueprints: &BlueprintsContext, stamps: &BTreeMap<PathBuf, Stamp>) {
+        let Some(set) = &self.set else {
+            return;
+        };
+
+        if self.lint.is_empty() {
+            println!("Lint: clean");
+        } else {
+            println!("Lint: {} error(s)", self.lint.len());
+            for violation in &self.lint {
+                println!("  {violation}");
+            }
+        }
+
+        let orphans = orphans(blueprints, set, self.tags.values().flatten());
+        let (label, shown): (&str, Vec<&String>) = match &self.orphans {
+            None => ("Orphans", orphans.iter().collect()),
+            Some(previous) => ("New orphans", orphans.difference(previous).collect()),
+        };
+        let resolved = self
+            .orphans
+            .as_ref()
+            .map_or(0, |previous| previous.difference(&orphans).count());
+        println!(
+            "{label}: {} ({} total, {resolved} resolved)",
+            shown.len(),
+            orphans.len()
+        );
+        for orphan in shown {
+            println!("  {orphan}");
+        }
+        self.orphans = Some(orphans);
+
+        let stale = stale_stages(blueprints, stamps);
+        if stale.is_empty() {
+            println!("Stale stages: none");
+        } else {
+            println!("Stale stages: {}", stale.len());
+            for stage in stale {
+                println!("  {stage}");
+            }
+        }
+    }
+}
+
+/// Orphan messages from [`findings::orphans`], without line numbers so that edits
+/// above them do not count as new orphans.
+pub(crate) fn orphans<'a>(
+    blueprints: &BlueprintsContext,
+    set: &BlueprintSet,
+    tags: impl Iterator<Item = &'a Tag>,
+) -> BTreeSet<String> {
+    findings::orphans(blueprints.dir(), blueprints.crate_dir(), set, tags)
+        .into_iter()
+        .map(|finding| finding.message)
+        .collect()
+}
+
+/// Stages whose output is older than one of its inputs.
+fn stale_stages(blueprints: &BlueprintsContext, stamps: &BTreeMap<PathBuf, Stamp>) -> Vec<String> {
+    let modified = |path: &Path| stamps.get(path).map(|(time, _)| *time);
+    let sources = stamps
+        .iter()
+        .filter(|(path, _)| !path.starts_with(blueprints.dir()))
+        .map(|(_, (time, _))| *time)
+        .max();
+
+    let mut stale = Vec::new();
+    for (stage, output, inputs) in STAGES {
+        let (output_name, output_time) = match output {
+            Some(file) => (file, modified(&blueprints.join(file))),
+            None => ("crate sources", sources),
+        };
+        let Some(output_time) = output_time else {
+            continue;
+        };
+        let newer: Vec<&str> = inputs
+            .iter()
+            .copied()
+            .filter(|file| modified(&blueprints.join(file)).is_some_and(|time| time > output_time))
+            .collect();
+        if !newer.is_empty() {
+            stale.push(format!(
+                "{stage}: {output_name} older than {}; rerun `blueprints {stage}`",
+                newer.join(", ")
+            ));
+        }
+    }
+    stale
+}
+
+/// Every watched file: the blueprints directory and the crate's Rust sources.
+fn stamps(blueprints: &BlueprintsContext) -> Result<BTreeMap<PathBuf, Stamp>> {
+    let mut files = tags::rust_files(blueprints.crate_dir())?;
+    if let Ok(entries) = fs::read_dir(blueprints.dir()) {
+        files.extend(
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file()),
+        );
+    }
+
+    Ok(files
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            let stamp = (metadata.modified().ok()?, metadata.len());
+            Some((path, stamp))
+        })
+        .collect())
+}
+
+/// Added, modified and deleted paths between two polls.
+fn changed_paths(
+    before: &BTreeMap<PathBuf, Stamp>,
+    after: &BTreeMap<PathBuf, Stamp>,
+) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, stamp)| before.get(*path) != Some(stamp))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    changed
+}
+
+fn display(blueprints: &BlueprintsContext, path: &Path) -> String {
+    path.strip_prefix(blueprints.crate_dir())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
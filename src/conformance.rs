@@ -0,0 +1,516 @@
+//! Structural conformance between `03-contracts.md` type blocks and the crate's
+//! items tagged `@contract(C-###)`.
+//!
+//! Both sides are parsed as Rust syntax. Structs, enums and type aliases are
+//! compared by name for visibility, derives, attributes and fields/variants.
+//! Type paths are compared without their module qualifiers, so
+//! `std::collections::HashMap<K, V>` matches `HashMap<K, V>`.
+
+use anyhow::{Context, Result};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::{
+    collections::BTreeSet,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+use syn::{Attribute, Fields, Item, punctuated::Punctuated};
+
+use crate::{
+    records::{CONTRACTS_FILE, contracts, read_optional},
+    tags::{self, TagKind},
+};
+
+/// Lint and documentation attributes are not part of a type's contract.
+const IGNORED_ATTRIBUTES: [&str; 7] = [
+    "doc", "allow", "expect", "warn", "deny", "forbid", "cfg_attr",
+];
+
+/// A struct, enum or type alias reduced to the parts a contract pins down.
+struct TypeShape {
+    kind: &'static str,
+    name: String,
+    line: usize,
+    vis: String,
+    derives: BTreeSet<String>,
+    attrs: BTreeSet<String>,
+    members: Vec<Member>,
+}
+
+/// A struct field (`ty` is the field type) or an enum variant (`ty` is its field list).
+struct Member {
+    name: String,
+    vis: String,
+    ty: String,
+    attrs: BTreeSet<String>,
+}
+
+struct ContractTypes {
+    id: String,
+    types: Vec<TypeShape>,
+    /// Blocks that failed to parse, as `03-contracts.md:<line>: <error>`.
+    parse_errors: Vec<String>,
+}
+
+struct TaggedType {
+    contracts: Vec<String>,
+    file: PathBuf,
+    shape: TypeShape,
+}
+
+/// Render the conformance report for the reviewer prompt; one `- ` line per finding.
+pub(crate) fn report(blueprints_dir: &Path, crate_dir: &Path) -> Result<String> {
+    let content = read_optional(&blueprints_dir.join(CONTRACTS_FILE))?;
+    let contracts = contract_types(&contracts::parse(&content));
+    let tagged = tagged_types(crate_dir)?;
+
+    let mut findings = Vec::new();
+
+    for contract in &contracts {
+        findings.extend(
+            contract
+                .parse_errors
+                .iter()
+                .map(|error| format!("{}: contract block does not parse: {error}", contract.id)),
+        );
+    }
+
+    for item in &tagged {
+        let location = format!("{}:{}", item.file.display(), item.shape.line);
+        for id in &item.contracts {
+            let Some(contract) = contracts.iter().find(|contract| &contract.id == id) else {
+                findings.push(format!(
+                    "{location} `{}` is tagged @contract({id}), which is not defined in {CONTRACTS_FILE}",
+                    item.shape.name
+                ));
+                continue;
+            };
+            if contract.types.is_empty() {
+                continue;
+            }
+            let Some(expected) = contract
+                .types
+                .iter()
+                .find(|shape| shape.name == item.shape.name)
+            else {
+                let defined: Vec<&str> = contract.types.iter().map(|t| t.name.as_str()).collect();
+                findings.push(format!(
+                    "{location} `{}` is tagged @contract({id}), but {id} defines no type with that name (defines: {})",
+                    item.shape.name,
+                    defined.join(", ")
+                ));
+                continue;
+            };
+            for mismatch in compare(expected, &item.shape) {
+                findings.push(format!(
+                    "{id} `{}` ({location} vs {CONTRACTS_FILE}:{}): {mismatch}",
+                    item.shape.name, expected.line
+                ));
+            }
+        }
+    }
+
+    for contract in &contracts {
+        for shape in &contract.types {
+            let implemented = tagged
+                .iter()
+                .any(|item| item.shape.name == shape.name && item.contracts.contains(&contract.id));
+            if !implemented {
+                findings.push(format!(
+                    "{} `{}` ({CONTRACTS_FILE}:{}): no {} tagged @{}({}) with this name was found",
+                    contract.id,
+                    shape.name,
+                    shape.line,
+                    shape.kind,
+                    TagKind::Contract.name(),
+                    contract.id
+                ));
+            }
+        }
+    }
+
+    if findings.is_empty() {
+        return Ok(if tagged.is_empty() {
+            "none (no types tagged @contract(C-###) found)".to_string()
+        } else {
+            format!(
+                "ok ({} tagged type(s) match {CONTRACTS_FILE})",
+                tagged.len()
+            )
+        });
+    }
+
+    let mut out = String::new();
+    for finding in findings {
+        let _ = writeln!(out, "- {finding}");
+    }
+    Ok(out.trim_end().to_string())
+}
+
+fn contract_types(parsed: &[contracts::Contract]) -> Vec<ContractTypes> {
+    parsed
+        .iter()
+        .map(|contract| {
+            let mut types = Vec::new();
+            let mut parse_errors = Vec::new();
+            for block in contract.code_blocks.iter().filter(|b| b.is_checked_rust()) {
+                match syn::parse_file(&block.code) {
+                    Ok(file) => {
+                        let mut shapes = Vec::new();
+                        collect_shapes(&file.items, &mut |_, shape| shapes.push(shape));
+                        for mut shape in shapes {
+                            shape.line += block.first_line - 1;
+                            types.push(shape);
+                        }
+                    }
+                    Err(error) => {
+                        let line = block.first_line + error.span().start().line - 1;
+                        parse_errors.push(format!("{CONTRACTS_FILE}:{line}: {error}"));
+                    }
+                }
+            }
+            ContractTypes {
+                id: contract.id.clone(),
+                types,
+                parse_errors,
+            }
+        })
+        .collect()
+}
+
+fn tagged_types(crate_dir: &Path) -> Result<Vec<TaggedType>> {
+    let mut tagged = Vec::new();
+    for path in tags::rust_files(crate_dir)? {
+        let relative = path.strip_prefix(crate_dir).unwrap_or(&path).to_path_buf();
+        if !relative.starts_with("src") {
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        // Sources that do not parse are reported by the compiler, not here.
+        let Ok(file) = syn::parse_file(&content) else {
+            continue;
+        };
+        collect_shapes(&file.items, &mut |attrs, shape| {
+            let contracts = contract_tags(attrs);
+            if !contracts.is_empty() {
+                tagged.push(TaggedType {
+                    contracts,
+                    file: relative.clone(),
+                    shape,
+                });
+            }
+        });
+    }
+    Ok(tagged)
+}
+
+/// Visit every struct, enum and type alias, descending into inline modules.
+fn collect_shapes(items: &[Item], visit: &mut impl FnMut(&[Attribute], TypeShape)) {
+    for item in items {
+        match item {
+            Item::Struct(item) => visit(
+                &item.attrs,
+                TypeShape {
+                    kind: "struct",
+                    name: item.ident.to_string(),
+                    line: item.ident.span().start().line,
+                    vis: tokens(&item.vis),
+                    derives: derives(&item.attrs),
+                    attrs: attributes(&item.attrs),
+                    members: fields(&item.fields),
+                },
+            ),
+            Item::Enum(item) => visit(
+                &item.attrs,
+                TypeShape {
+                    kind: "enum",
+                    name: item.ident.to_string(),
+                    line: item.ident.span().start().line,
+                    vis: tokens(&item.vis),
+                    derives: derives(&item.attrs),
+                    attrs: attributes(&item.attrs),
+                    members: item
+                        .variants
+                        .iter()
+                        .map(|variant| Member {
+                            name: variant.ident.to_string(),
+                            vis: String::new(),
+                            ty: variant_shape(&variant.fields),
+                            attrs: attributes(&variant.attrs),
+                        })
+                        .collect(),
+                },
+            ),
+            Item::Type(item) => visit(
+                &item.attrs,
+                TypeShape {
+                    kind: "type alias",
+                    name: item.ident.to_string(),
+                    line: item.ident.span().start().line,
+                    vis: tokens(&item.vis),
+                    derives: BTreeSet::new(),
+                    attrs: attributes(&item.attrs),
+                    members: vec![Member {
+                        name: "=".to_string(),
+                        vis: String::new(),
+                        ty: tokens(&item.ty),
+                        attrs: BTreeSet::new(),
+                    }],
+                },
+            ),
+            Item::Mod(item) => {
+                if let Some((_, items)) = &item.content {
+                    collect_shapes(items, visit);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn compare(expected: &TypeShape, actual: &TypeShape) -> Vec<String> {
+    let mut mismatches = Vec::new();
+
+    if expected.kind != actual.kind {
+        mismatches.push(format!(
+            "kind: contract `{}`, code `{}`",
+            expected.kind, actual.kind
+        ));
+        return mismatches;
+    }
+
+    if expected.vis != actual.vis {
+        mismatches.push(format!(
+            "visibility: contract `{}`, code `{}`",
+            display_vis(&expected.vis),
+            display_vis(&actual.vis)
+        ));
+    }
+
+    set_diff(
+        &mut mismatches,
+        "",
+        "derive",
+        &expected.derives,
+        &actual.derives,
+    );
+    set_diff(
+        &mut mismatches,
+        "",
+        "attribute",
+        &expected.attrs,
+        &actual.attrs,
+    );
+
+    let label = if expected.kind == "enum" {
+        "variant"
+    } else {
+        "field"
+    };
+    for member in &expected.members {
+        let Some(found) = actual.members.iter().find(|m| m.name == member.name) else {
+            mismatches.push(format!("missing {label} `{}`", member.name));
+            continue;
+        };
+        let subject = if expected.kind == "type alias" {
+            "aliased type".to_string()
+        } else {
+            format!("{label} `{}`", member.name)
+        };
+        if member.ty != found.ty {
+            let (what, unit) = if label == "variant" {
+                ("fields", "unit")
+            } else {
+                ("type", "")
+            };
+            let show = |ty: &str| {
+                if ty.is_empty() {
+                    unit.to_string()
+                } else {
+                    ty.to_string()
+                }
+            };
+            mismatches.push(format!(
+                "{subject} {what}: contract `{}`, code `{}`",
+                show(&member.ty),
+                show(&found.ty)
+            ));
+        }
+        if member.vis != found.vis {
+            mismatches.push(format!(
+                "{subject} visibility: contract `{}`, code `{}`",
+                display_vis(&member.vis),
+                display_vis(&found.vis)
+            ));
+        }
+        set_diff(
+            &mut mismatches,
+            &format!("{subject}: "),
+            "attribute",
+            &member.attrs,
+            &found.attrs,
+        );
+    }
+    for member in &actual.members {
+        if !expected.members.iter().any(|m| m.name == member.name) {
+            mismatches.push(format!("extra {label} `{}`", member.name));
+        }
+    }
+
+    mismatches
+}
+
+fn set_diff(
+    mismatches: &mut Vec<String>,
+    prefix: &str,
+    what: &str,
+    expected: &BTreeSet<String>,
+    actual: &BTreeSet<String>,
+) {
+    for missing in expected.difference(actual) {
+        mismatches.push(format!("{prefix}missing {what} `{missing}`"));
+    }
+    for extra in actual.difference(expected) {
+        mismatches.push(format!("{prefix}extra {what} `{extra}`"));
+    }
+}
+
+fn display_vis(vis: &str) -> &str {
+    if vis.is_empty() { "private" } else { vis }
+}
+
+fn fields(fields: &Fields) -> Vec<Member> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| Member {
+            name: field
+                .ident
+                .as_ref()
+                .map_or_else(|| idx.to_string(), ToString::to_string),
+            vis: tokens(&field.vis),
+            ty: tokens(&field.ty),
+            attrs: attributes(&field.attrs),
+        })
+        .collect()
+}
+
+/// `(u32,String)`, `{code:u16,message:String}` or empty for unit variants.
+fn variant_shape(fields: &Fields) -> String {
+    let render = |field: &syn::Field| match &field.ident {
+        Some(name) => format!("{name}:{}", tokens(&field.ty)),
+        None => tokens(&field.ty),
+    };
+    match fields {
+        Fields::Named(named) => format!(
+            "{{{}}}",
+            named.named.iter().map(render).collect::<Vec<_>>().join(",")
+        ),
+        Fields::Unnamed(unnamed) => format!(
+            "({})",
+            unnamed
+                .unnamed
+                .iter()
+                .map(render)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        Fields::Unit => String::new(),
+    }
+}
+
+fn derives(attrs: &[Attribute]) -> BTreeSet<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter_map(|path| {
+            path.segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+        })
+        .collect()
+}
+
+fn attributes(attrs: &[Attribute]) -> BTreeSet<String> {
+    attrs
+        .iter()
+        .filter(|attr| {
+            let path = attr.path();
+            !path.is_ident("derive") && !IGNORED_ATTRIBUTES.iter().any(|name| path.is_ident(name))
+        })
+        .map(|attr| format!("#[{}]", tokens(&attr.meta)))
+        .collect()
+}
+
+fn contract_tags(attrs: &[Attribute]) -> Vec<String> {
+    tags::doc_tags(attrs)
+        .into_iter()
+        .filter(|(kind, _)| *kind == TagKind::Contract)
+        .flat_map(|(_, ids)| ids)
+        .collect()
+}
+
+/// Canonical token rendering with module qualifiers dropped: no spaces except
+/// between words (`&'a str`, `dyn Fn`), literals kept verbatim.
+fn tokens(node: &impl ToTokens) -> String {
+    let mut out = String::new();
+    render(node.to_token_stream(), &mut out);
+    out
+}
+
+fn render(stream: TokenStream, out: &mut String) {
+    let trees: Vec<TokenTree> = stream.into_iter().collect();
+    let mut index = 0;
+    while index < trees.len() {
+        // `a::b::C` renders as `C`; a leading `::` is dropped too.
+        if path_separator(&trees[index..]) {
+            index += 2;
+            continue;
+        }
+        if matches!(trees[index], TokenTree::Ident(_)) && path_separator(&trees[index + 1..]) {
+            index += 3;
+            continue;
+        }
+
+        match &trees[index] {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                out.push_str(open);
+                render(group.stream(), out);
+                out.push_str(close);
+            }
+            TokenTree::Ident(ident) => push_word(out, &ident.to_string()),
+            TokenTree::Literal(literal) => push_word(out, &literal.to_string()),
+            TokenTree::Punct(punct) => out.push(punct.as_char()),
+        }
+        index += 1;
+    }
+}
+
+fn path_separator(trees: &[TokenTree]) -> bool {
+    matches!(
+        trees,
+        [TokenTree::Punct(first), TokenTree::Punct(second), ..]
+            if first.as_char() == ':' && first.spacing() == Spacing::Joint && second.as_char() == ':'
+    )
+}
+
+/// Words need a separating space only after another word.
+fn push_word(out: &mut String, word: &str) {
+    if out.ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '"') {
+        out.push(' ');
+    }
+    out.push_str(word);
+}
@@ -0,0 +1,323 @@
+//! Compile-check for the Rust code blocks in `03-contracts.md`.
+//!
+//! Every checked `rust` block is copied into a scratch library crate that carries
+//! the target crate's dependencies; `cargo check` diagnostics on that crate are
+//! mapped back to the contract ID and Markdown line they came from.
+
+use anyhow::{Context, Result, anyhow};
+use std::{
+    collections::BTreeSet,
+    env,
+    fmt::{self, Write as _},
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::records::contracts::Contract;
+
+/// Scratch crates live under `<target-dir>/blueprints/contracts-check/<package>/`.
+const SCRATCH_DIR: &str = "blueprints/contracts-check";
+
+const LIB_HEADER: &str = "\
+// Generated by `blueprints contracts check` from 03-contracts.md; do not edit.
+#![allow(dead_code, unused_imports, unused_variables)]
+";
+
+/// A `cargo check` error located in `03-contracts.md`.
+pub(crate) struct ContractDiagnostic {
+    pub(crate) contract: String,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for ContractDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.line, self.column, self.contract, self.message
+        )
+    }
+}
+
+pub(crate) enum CheckOutcome {
+    /// No contract has a checked `rust` block.
+    NoBlocks,
+    /// `cargo check` ran; an empty list means every block compiled.
+    Checked(Vec<ContractDiagnostic>),
+}
+
+/// The generated `src/lib.rs` plus, per line, the contract and Markdown line it came from.
+struct ScratchSource {
+    code: String,
+    origins: Vec<Option<(String, usize)>>,
+}
+
+impl ScratchSource {
+    fn push(&mut self, line: &str, origin: Option<(String, usize)>) {
+        self.code.push_str(line);
+        self.code.push('\n');
+        self.origins.push(origin);
+    }
+
+    fn origin(&self, line: usize) -> Option<&(String, usize)> {
+        self.origins.get(line.checked_sub(1)?)?.as_ref()
+    }
+}
+
+/// Compile every checked contract block for the crate at `crate_dir`.
+pub(crate) fn check(
+    contracts: &[Contract],
+    package: &str,
+    crate_dir: &Path,
+    workspace_root: &Path,
+) -> Result<CheckOutcome> {
+    let Some(source) = build_source(contracts) else {
+        return Ok(CheckOutcome::NoBlocks);
+    };
+
+    let scratch = target_dir(workspace_root).join(SCRATCH_DIR).join(package);
+    fs::create_dir_all(scratch.join("src"))
+        .with_context(|| format!("failed to create {}", scratch.display()))?;
+
+    fs::write(
+        scratch.join("Cargo.toml"),
+        scratch_manifest(package, crate_dir, workspace_root),
+    )
+    .with_context(|| format!("failed to write {}", scratch.join("Cargo.toml").display()))?;
+    fs::write(scratch.join("src/lib.rs"), &source.code)
+        .with_context(|| format!("failed to write {}", scratch.join("src/lib.rs").display()))?;
+
+    // Reuse the workspace lockfile so the scratch crate resolves the same versions.
+    let lockfile = workspace_root.join("Cargo.lock");
+    if lockfile.is_file() && !scratch.join("Cargo.lock").exists() {
+        fs::copy(&lockfile, scratch.join("Cargo.lock"))
+            .with_context(|| format!("failed to copy {}", lockfile.display()))?;
+    }
+
+    let output = Command::new("cargo")
+        .args([
+            "check",
+            "--quiet",
+            "--message-format=short",
+            "--manifest-path",
+        ])
+        .arg(scratch.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(scratch.join("target"))
+        .output()
+        .context("failed to execute cargo check")?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let diagnostics: Vec<ContractDiagnostic> = stderr
+        .lines()
+        .filter_map(|line| parse_diagnostic(line, &source))
+        .collect();
+
+    if !output.status.success() && diagnostics.is_empty() {
+        return Err(anyhow!(
+            "cargo check failed on the scratch crate at {}:\n{}",
+            scratch.display(),
+            stderr.trim_end()
+        ));
+    }
+
+    Ok(CheckOutcome::Checked(diagnostics))
+}
+
+fn target_dir(workspace_root: &Path) -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR").map_or_else(|| workspace_root.join("target"), PathBuf::from)
+}
+
+/// Concatenate every checked block, hoisting top-level `use` lines so blocks that
+/// repeat the same import do not collide.
+fn build_source(contracts: &[Contract]) -> Option<ScratchSource> {
+    let blocks: Vec<_> = contracts
+        .iter()
+        .flat_map(|contract| {
+            contract
+                .code_blocks
+                .iter()
+                .filter(|block| block.is_checked_rust())
+                .map(move |block| (contract.id.as_str(), block))
+        })
+        .collect();
+    if blocks.is_empty() {
+        return None;
+    }
+
+    let mut source = ScratchSource {
+        code: String::new(),
+        origins: Vec::new(),
+    };
+    for line in LIB_HEADER.lines() {
+        source.push(line, None);
+    }
+
+    let mut hoisted = BTreeSet::new();
+    for (id, block) in &blocks {
+        for (offset, line) in block.code.lines().enumerate() {
+            if line.starts_with("use ") && line.ends_with(';') && hoisted.insert(line) {
+                source.push(line, Some(((*id).to_string(), block.first_line + offset)));
+            }
+        }
+    }
+
+    for (id, block) in blocks {
+        source.push("", None);
+        source.push(
+            &format!("// {id} (03-contracts.md:{})", block.first_line),
+            None,
+        );
+        for (offset, line) in block.code.lines().enumerate() {
+            let hoisted_use = line.starts_with("use ") && line.ends_with(';');
+            let origin = Some((id.to_string(), block.first_line + offset));
+            source.push(if hoisted_use { "" } else { line }, origin);
+        }
+    }
+
+    Some(source)
+}
+
+/// Parse `src/lib.rs:12:5: error[E0412]: ...` from `--message-format=short` output.
+fn parse_diagnostic(line: &str, source: &ScratchSource) -> Option<ContractDiagnostic> {
+    let rest = line.strip_prefix("src/lib.rs:")?;
+    let mut parts = rest.splitn(3, ':');
+    let number: usize = parts.next()?.parse().ok()?;
+    let column: usize = parts.next()?.parse().ok()?;
+    let message = parts.next()?.trim();
+    if !message.starts_with("error") {
+        return None;
+    }
+
+    let (contract, line) = source.origin(number)?;
+    Some(ContractDiagnostic {
+        contract: contract.clone(),
+        line: *line,
+        column,
+        message: message.to_string(),
+    })
+}
+
+/// Manifest for the scratch crate: a standalone workspace carrying the target crate's
+/// dependency tables and the root workspace's shared dependencies.
+fn scratch_manifest(package: &str, crate_dir: &Path, workspace_root: &Path) -> String {
+    let crate_manifest = fs::read_to_string(crate_dir.join("Cargo.toml")).unwrap_or_default();
+    let root_manifest = fs::read_to_string(workspace_root.join("Cargo.toml")).unwrap_or_default();
+
+    let edition = manifest_value(&crate_manifest, "package", "edition")
+        .or_else(|| manifest_value(&root_manifest, "workspace.package", "edition"))
+        .unwrap_or_else(|| "2021".to_string());
+
+    let mut manifest = format!(
+        "[package]\nname = \"{package}-contracts-check\"\nversion = \"0.0.0\"\nedition = \"{edition}\"\npublish = false\n\n[lib]\npath = \"src/lib.rs\"\n\n[workspace]\n"
+    );
+
+    for (header, body) in tables(&root_manifest) {
+        if header == "workspace.dependencies" || header.starts_with("workspace.dependencies.") {
+            let _ = write!(manifest, "\n[{header}]\n");
+            manifest.push_str(&absolutize_paths(&body, workspace_root));
+        }
+    }
+    for (header, body) in tables(&crate_manifest) {
+        if is_dependency_table(&header) {
+            let _ = write!(manifest, "\n[{header}]\n");
+            manifest.push_str(&absolutize_paths(&body, crate_dir));
+        }
+    }
+
+    manifest
+}
+
+/// `[dependencies]`, `[dependencies.foo]` and their `[target.'cfg(..)'.*]` forms.
+fn is_dependency_table(header: &str) -> bool {
+    let header = match header.strip_prefix("target.") {
+        Some(rest) => rest
+            .split_once("].")
+            .or_else(|| rest.split_once("'."))
+            .or_else(|| rest.split_once("\"."))
+            .map_or(rest, |(_, after)| after),
+        None => header,
+    };
+    header == "dependencies" || header.starts_with("dependencies.")
+}
+
+/// Top-level tables of a manifest as `(header, body)` pairs, in file order.
+fn tables(manifest: &str) -> Vec<(String, String)> {
+    let mut tables: Vec<(String, String)> = Vec::new();
+    for raw in manifest.lines() {
+        let line = raw.trim();
+        if line.starts_with('[') && !line.starts_with("[[") && line.ends_with(']') {
+            let header = line.trim_start_matches('[').trim_end_matches(']').trim();
+            tables.push((header.to_string(), String::new()));
+            continue;
+        }
+        if let Some((_, body)) = tables.last_mut() {
+            body.push_str(raw);
+            body.push('\n');
+        }
+    }
+    tables
+}
+
+fn manifest_value(manifest: &str, table: &str, key: &str) -> Option<String> {
+    let (_, body) = tables(manifest)
+        .into_iter()
+        .find(|(header, _)| header == table)?;
+    body.lines().find_map(|line| {
+        let (name, value) = line.split_once('=')?;
+        let value = value.trim();
+        (name.trim() == key && value.starts_with('"')).then(|| value.trim_matches('"').to_string())
+    })
+}
+
+/// Rewrite `path = "../x"` dependency paths relative to `base` as absolute paths.
+fn absolutize_paths(body: &str, base: &Path) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut rest = body;
+
+    while let Some(pos) = find_path_key(rest) {
+        let (before, after) = rest.split_at(pos);
+        out.push_str(before);
+        let Some(open) = after.find('"') else {
+            rest = after;
+            break;
+        };
+        let Some(close) = after[open + 1..].find('"') else {
+            rest = after;
+            break;
+        };
+        let value = &after[open + 1..open + 1 + close];
+        let resolved = if Path::new(value).is_absolute() {
+            PathBuf::from(value)
+        } else {
+            base.join(value)
+        };
+        out.push_str(&after[..=open]);
+        out.push_str(&resolved.to_string_lossy().replace('\\', "/"));
+        rest = &after[open + 1 + close..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Byte offset of the next `path` key (not e.g. `xpath`) followed by `=`.
+fn find_path_key(text: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(pos) = text[offset..].find("path") {
+        let start = offset + pos;
+        let preceded = text[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || c == '{' || c == ',');
+        let followed = text[start + 4..].trim_start().starts_with('=');
+        if preceded && followed {
+            return Some(start);
+        }
+        offset = start + 4;
+    }
+    None
+}
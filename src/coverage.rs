@@ -0,0 +1,190 @@
+//! Per-spec-clause line coverage: `cargo llvm-cov` results joined with the
+//! `@s(S-...)` and `@impl(R-...)` tagged items of the target crate.
+//!
+//! Items tagged `@s` count toward those clauses. Items tagged only `@impl(R-...)`
+//! count toward every clause that references one of those requirements.
+
+use anyhow::{Context, Result, anyhow};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    records::{BlueprintSet, lifecycle::Status},
+    tags,
+};
+
+/// The LCOV report is written under `<workspace>/target/`.
+const LCOV_PATH: &str = "target/blueprints/coverage.lcov";
+
+struct ClauseCoverage {
+    id: String,
+    title: String,
+    covered: usize,
+    total: usize,
+}
+
+impl ClauseCoverage {
+    fn percent(&self) -> Option<f64> {
+        (self.total > 0).then(|| ratio(self.covered, self.total))
+    }
+}
+
+pub(crate) struct CoverageReport {
+    /// Per-clause table for `HOST_CI_RESULTS`.
+    pub(crate) table: String,
+    /// Remaining-work lines for clauses below the threshold; empty when all pass.
+    pub(crate) gaps: Vec<String>,
+}
+
+/// Run `cargo llvm-cov nextest` for `package` and build the per-clause report.
+pub(crate) fn run(
+    package: &str,
+    blueprints_dir: &Path,
+    crate_dir: &Path,
+    threshold: f64,
+) -> Result<CoverageReport> {
+    if let Some(parent) = Path::new(LCOV_PATH).parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+
+    let output = Command::new("cargo")
+        .args([
+            "llvm-cov",
+            "nextest",
+            "-p",
+            package,
+            "--all-features",
+            "--lcov",
+            "--output-path",
+            LCOV_PATH,
+        ])
+        .output()
+        .context("failed to run cargo llvm-cov")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "cargo llvm-cov failed (is cargo-llvm-cov installed?):\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        ));
+    }
+
+    let lcov =
+        fs::read_to_string(LCOV_PATH).with_context(|| format!("failed to read {LCOV_PATH}"))?;
+    let hits = parse_lcov(&lcov, crate_dir);
+    let set = BlueprintSet::load(blueprints_dir)?;
+    let spans = tags::tagged_spans(crate_dir)?;
+
+    let clauses: Vec<ClauseCoverage> = set
+        .spec
+        .clauses
+        .iter()
+        .filter(|clause| set.status_of(&clause.id) != Status::Removed)
+        .map(|clause| {
+            let mut lines: BTreeSet<(&Path, usize)> = BTreeSet::new();
+            for span in spans.iter().filter(|span| span.traces_to(clause)) {
+                lines.extend((span.start..=span.end).map(|line| (span.file.as_path(), line)));
+            }
+
+            let (mut covered, mut total) = (0, 0);
+            for (file, line) in lines {
+                if let Some(count) = hits.get(file).and_then(|lines| lines.get(&line)) {
+                    total += 1;
+                    if *count > 0 {
+                        covered += 1;
+                    }
+                }
+            }
+            ClauseCoverage {
+                id: clause.id.clone(),
+                title: clause.title().unwrap_or_default().to_string(),
+                covered,
+                total,
+            }
+        })
+        .collect();
+
+    Ok(CoverageReport {
+        table: render_table(&clauses, threshold),
+        gaps: gaps(&clauses, threshold),
+    })
+}
+
+fn render_table(clauses: &[ClauseCoverage], threshold: f64) -> String {
+    let mut table = format!("CLAUSE_COVERAGE (threshold {threshold:.1}%)");
+    for clause in clauses {
+        let status = match clause.percent() {
+            Some(percent) if percent >= threshold => "ok",
+            Some(_) => "below",
+            None => "untraced",
+        };
+        let percent = clause
+            .percent()
+            .map_or_else(|| "-".to_string(), |p| format!("{p:.1}%"));
+        let _ = write!(
+            table,
+            "\n{} {}/{} {percent} {status}",
+            clause.id, clause.covered, clause.total
+        );
+    }
+    table
+}
+
+fn gaps(clauses: &[ClauseCoverage], threshold: f64) -> Vec<String> {
+    clauses
+        .iter()
+        .filter_map(|clause| match clause.percent() {
+            Some(percent) if percent >= threshold => None,
+            Some(percent) => Some(format!(
+                "COVERAGE: {} ({}) is {percent:.1}% line-covered ({}/{} lines), below {threshold:.1}%; add or strengthen tests that exercise it.",
+                clause.id, clause.title, clause.covered, clause.total
+            )),
+            None => Some(format!(
+                "COVERAGE: {} ({}) has no executable code tagged `@s({})` or `@impl` for its requirements; tag the implementing items or implement the clause.",
+                clause.id, clause.title, clause.id
+            )),
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ratio(covered: usize, total: usize) -> f64 {
+    covered as f64 * 100.0 / total as f64
+}
+
+/// `DA:<line>,<count>` records per crate-relative source file.
+fn parse_lcov(lcov: &str, crate_dir: &Path) -> BTreeMap<PathBuf, BTreeMap<usize, u64>> {
+    let crate_dir = crate_dir
+        .canonicalize()
+        .unwrap_or_else(|_| crate_dir.to_path_buf());
+    let mut files: BTreeMap<PathBuf, BTreeMap<usize, u64>> = BTreeMap::new();
+    let mut current: Option<PathBuf> = None;
+
+    for line in lcov.lines() {
+        if let Some(path) = line.strip_prefix("SF:") {
+            current = Path::new(path)
+                .strip_prefix(&crate_dir)
+                .ok()
+                .map(Path::to_path_buf);
+        } else if line == "end_of_record" {
+            current = None;
+        } else if let (Some(file), Some(data)) = (&current, line.strip_prefix("DA:")) {
+            let mut fields = data.split(',');
+            let line_no = fields.next().and_then(|v| v.parse().ok());
+            let count: Option<u64> = fields.next().and_then(|v| v.parse().ok());
+            if let (Some(line_no), Some(count)) = (line_no, count) {
+                *files
+                    .entry(file.clone())
+                    .or_default()
+                    .entry(line_no)
+                    .or_default() += count;
+            }
+        }
+    }
+
+    files
+}
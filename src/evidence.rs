@@ -0,0 +1,98 @@
+//! Host-side evidence check for checked-off Delivery Plan items: every referenced
+//! `TV-###` needs a test tagged `@tv(TV-###)` and every referenced `S-###` needs
+//! non-test code tagged `@s(S-###)`, unless the record is removed in the lifecycle ledger.
+
+use anyhow::Result;
+use std::{fmt, path::Path};
+
+use crate::{
+    records::{
+        BlueprintSet, DELIVERY_PLAN_FILE, IdKind,
+        delivery::{self, DeliveryItem},
+        lifecycle::Status,
+        read_optional,
+    },
+    tags::{self, Tag, TagKind},
+};
+
+/// A `- [x]` item whose references lack tagged tests or code.
+pub(crate) struct Unsupported {
+    pub(crate) item: DeliveryItem,
+    pub(crate) missing: Vec<String>,
+}
+
+impl Unsupported {
+    /// Remaining-work line that reopens the item for the builder.
+    pub(crate) fn reopen(&self) -> String {
+        format!(
+            "{} (REOPENED: checked off without evidence: {})",
+            self.item.text,
+            self.missing.join("; ")
+        )
+    }
+}
+
+/// `<line>: DP-###: <missing>`; callers prefix the delivery plan path.
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}",
+            self.item.line,
+            self.item.id,
+            self.missing.join("; ")
+        )
+    }
+}
+
+/// Checked items of the delivery plan and those among them without evidence.
+pub(crate) fn verify(blueprints_dir: &Path, crate_dir: &Path) -> Result<(usize, Vec<Unsupported>)> {
+    let items = delivery::parse(&read_optional(&blueprints_dir.join(DELIVERY_PLAN_FILE))?);
+    let checked: Vec<DeliveryItem> = items.into_iter().filter(|item| item.done).collect();
+    if checked.is_empty() {
+        return Ok((0, Vec::new()));
+    }
+
+    let set = BlueprintSet::load(blueprints_dir)?;
+    let tags = tags::scan_crate(crate_dir)?;
+    let total = checked.len();
+    let unsupported = checked
+        .into_iter()
+        .filter_map(|item| {
+            let missing: Vec<String> = item
+                .refs
+                .iter()
+                // Removed records take their tags with them; they need no evidence.
+                .filter(|id| set.status_of(id) != Status::Removed)
+                .filter_map(|id| missing_evidence(&tags, id))
+                .collect();
+            (!missing.is_empty()).then_some(Unsupported { item, missing })
+        })
+        .collect();
+
+    Ok((total, unsupported))
+}
+
+/// What is missing for `id` to count as done: a tagged test for a `TV-###`, tagged
+/// non-test code for an `S-###`. Other kinds need no evidence.
+pub(crate) fn missing_evidence(tags: &[Tag], id: &str) -> Option<String> {
+    match IdKind::of(id)? {
+        IdKind::TestVector => (!tags.iter().any(|tag| {
+            tag.mentions(TagKind::TestVector, id) && tag.item.as_ref().is_some_and(|i| i.is_test)
+        }))
+        .then(|| format!("no test tagged `@tv({id})`")),
+        IdKind::Spec => (!tags
+            .iter()
+            .any(|tag| tag.mentions(TagKind::Spec, id) && is_code(tag)))
+        .then(|| format!("no code tagged `@s({id})`")),
+        _ => None,
+    }
+}
+
+/// Non-test items, or module-level `//!` tags outside `tests/`.
+fn is_code(tag: &Tag) -> bool {
+    match &tag.item {
+        Some(item) => !item.is_test,
+        None => !tag.file.starts_with("tests"),
+    }
+}
@@ -0,0 +1,284 @@
+//! Lint, traceability and history findings, rendered as log lines, JSON or
+//! SARIF 2.1.0 for code-review tooling. Their rule IDs live in `records::rules`.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde_json::{Value, json};
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    logging::{log_blueprints, log_error},
+    records::{
+        BlueprintSet, IdKind, REQUIREMENTS_FILE, SPEC_FILE, TEST_VECTORS_FILE,
+        lifecycle::Status,
+        rules::{Level, Rule},
+        schema::Violation,
+    },
+    tags::Tag,
+};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lower")]
+pub(crate) enum Format {
+    /// Log lines for people.
+    #[default]
+    Text,
+    /// One JSON document with every finding.
+    Json,
+    /// SARIF 2.1.0 for code-review tooling.
+    Sarif,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Finding {
+    pub(crate) rule: Rule,
+    pub(crate) path: PathBuf,
+    /// 1-based.
+    pub(crate) line: usize,
+    pub(crate) message: String,
+}
+
+impl Finding {
+    /// A schema or history violation of a file in `blueprints_dir`.
+    pub(crate) fn of_violation(blueprints_dir: &Path, violation: Violation) -> Self {
+        Self {
+            rule: violation.rule,
+            path: blueprints_dir.join(violation.file),
+            line: violation.line,
+            message: violation.message,
+        }
+    }
+}
+
+/// Active records nothing downstream traces to, and code tags naming undefined IDs.
+///
+/// Messages carry no line numbers so that `watch` can compare them across edits.
+pub(crate) fn orphans<'a>(
+    blueprints_dir: &Path,
+    crate_dir: &Path,
+    set: &BlueprintSet,
+    tags: impl Iterator<Item = &'a Tag>,
+) -> Vec<Finding> {
+    let active = |id: &str| set.status_of(id) != Status::Removed;
+    let mut findings = Vec::new();
+
+    for requirement in set.requirements.iter().filter(|r| active(&r.id)) {
+        if !set
+            .spec
+            .clauses
+            .iter()
+            .any(|clause| active(&clause.id) && clause.requirements.contains(&requirement.id))
+        {
+            findings.push(Finding {
+                rule: Rule::OrphanRequirement,
+                path: blueprints_dir.join(REQUIREMENTS_FILE),
+                line: requirement.line,
+                message: format!("{}: not covered by any spec clause", requirement.id),
+            });
+        }
+    }
+
+    for clause in set.spec.clauses.iter().filter(|c| active(&c.id)) {
+        if !set
+            .test_vectors
+            .iter()
+            .any(|tv| active(&tv.id) && tv.specs.contains(&clause.id))
+        {
+            findings.push(Finding {
+                rule: Rule::UntestedClause,
+                path: blueprints_dir.join(SPEC_FILE),
+                line: clause.line,
+                message: format!("{}: no test vector", clause.id),
+            });
+        }
+    }
+
+    if !set.delivery.is_empty() {
+        for tv in set.test_vectors.iter().filter(|tv| active(&tv.id)) {
+            if !set.delivery.iter().any(|item| item.refs.contains(&tv.id)) {
+                findings.push(Finding {
+                    rule: Rule::UnplannedTestVector,
+                    path: blueprints_dir.join(TEST_VECTORS_FILE),
+                    line: tv.line,
+                    message: format!("{}: not referenced by the delivery plan", tv.id),
+                });
+            }
+        }
+    }
+
+    for tag in tags {
+        for id in tag
+            .ids
+            .iter()
+            .filter(|id| IdKind::of(id).is_some() && !set.defines(id))
+        {
+            findings.push(Finding {
+                rule: Rule::UndefinedTagId,
+                path: crate_dir.join(&tag.file),
+                line: tag.line,
+                message: format!(
+                    "{}: @{}({id}) names an undefined ID",
+                    tag.file.display(),
+                    tag.kind.name()
+                ),
+            });
+        }
+    }
+
+    findings
+}
+
+/// Print `findings` in `format`: text goes to the log, JSON and SARIF to stdout.
+pub(crate) fn emit(findings: &[Finding], format: Format) -> Result<()> {
+    let root = env::current_dir()?;
+    match format {
+        Format::Text => {
+            for finding in findings {
+                let line = format!(
+                    "{}:{}: {} [{} {}]",
+                    relative(&root, &finding.path),
+                    finding.line,
+                    finding.message,
+                    finding.rule.id(),
+                    finding.rule.name()
+                );
+                match finding.rule.level() {
+                    Level::Error => log_error(line),
+                    Level::Warning => log_blueprints(format!("warning: {line}")),
+                }
+            }
+        }
+        Format::Json => {
+            let findings: Vec<Value> = findings
+                .iter()
+                .map(|finding| {
+                    let region = region(finding);
+                    json!({
+                        "rule": finding.rule.id(),
+                        "name": finding.rule.name(),
+                        "level": finding.rule.level().as_str(),
+                        "path": relative(&root, &finding.path),
+                        "line": finding.line,
+                        "column": region["startColumn"],
+                        "end_column": region["endColumn"],
+                        "message": finding.message,
+                    })
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json!({ "findings": findings }))?
+            );
+        }
+        Format::Sarif => println!("{}", serde_json::to_string_pretty(&sarif(&root, findings))?),
+    }
+    Ok(())
+}
+
+pub(crate) fn errors(findings: &[Finding]) -> usize {
+    findings
+        .iter()
+        .filter(|finding| finding.rule.level() == Level::Error)
+        .count()
+}
+
+/// A SARIF log with one run; locations are relative to the workspace root `root`.
+fn sarif(root: &Path, findings: &[Finding]) -> Value {
+    let rules: Vec<Value> = Rule::ALL
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id(),
+                "name": rule.name(),
+                "shortDescription": { "text": rule.description() },
+                "defaultConfiguration": { "level": rule.level().as_str() },
+            })
+        })
+        .collect();
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            json!({
+                "ruleId": finding.rule.id(),
+                "ruleIndex": Rule::ALL.iter().position(|rule| *rule == finding.rule),
+                "level": finding.rule.level().as_str(),
+                "message": { "text": finding.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": uri(&relative(root, &finding.path)),
+                            "uriBaseId": "SRCROOT",
+                        },
+                        "region": region(finding),
+                    },
+                }],
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "blueprints",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": {
+                "SRCROOT": { "uri": format!("file://{}/", uri(&root.display().to_string())) },
+            },
+            "columnKind": "utf16CodeUnits",
+            "results": results,
+        }],
+    })
+}
+
+/// The finding's line without indentation and trailing whitespace, in UTF-16 columns.
+fn region(finding: &Finding) -> Value {
+    let text = fs::read_to_string(&finding.path).unwrap_or_default();
+    let line = text
+        .lines()
+        .nth(finding.line.saturating_sub(1))
+        .unwrap_or_default();
+    let content = line.trim();
+    let indent = line.len() - line.trim_start().len();
+    let start = line[..indent].encode_utf16().count() + 1;
+    let end = start + content.encode_utf16().count();
+    json!({
+        "startLine": finding.line.max(1),
+        "startColumn": start,
+        "endLine": finding.line.max(1),
+        "endColumn": end.max(start + 1),
+        "snippet": { "text": content },
+    })
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Percent-encode everything but unreserved characters and `/`.
+fn uri(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
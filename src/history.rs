@@ -0,0 +1,201 @@
+//! Append-only and ID-stability rules from `BLUEPRINTS.md`, checked against a git
+//! base revision: the lifecycle ledger only grows, retired IDs are never reused,
+//! S clauses are never renumbered, and R-ids leave `01-requirements.md` only with
+//! a `STATUS:removed` ledger record.
+
+use anyhow::Result;
+use std::{collections::BTreeMap, path::Path, process::Command};
+
+use crate::records::{
+    BlueprintSet, IdKind, LIFECYCLE_FILE, REQUIREMENTS_FILE, SPEC_FILE, ids,
+    lifecycle::{self, Status},
+    read_optional,
+    rules::Rule,
+    schema::Violation,
+    spec,
+};
+
+/// Kinds whose IDs `BLUEPRINTS.md` declares never-reused.
+const STABLE_KINDS: [IdKind; 4] = [
+    IdKind::Requirement,
+    IdKind::Spec,
+    IdKind::Contract,
+    IdKind::TestVector,
+];
+
+/// The commit `rev` names, if it resolves in the repository containing `dir`.
+pub(crate) fn resolve(dir: &Path, rev: &str) -> Option<String> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{rev}^{{commit}}"))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Violations of the working-tree blueprints against commit `base`.
+pub(crate) fn check(dir: &Path, base: &str) -> Result<Vec<Violation>> {
+    let current = BlueprintSet::load(dir)?;
+    let mut violations = check_lifecycle(dir, base)?;
+
+    for kind in STABLE_KINDS {
+        violations.extend(check_reuse(dir, base, kind)?);
+    }
+    violations.extend(check_renumbering(dir, base)?);
+    violations.extend(check_removed_requirements(dir, base, &current));
+
+    Ok(violations)
+}
+
+/// Every ledger line at `base` must still be present, unchanged and in order.
+fn check_lifecycle(dir: &Path, base: &str) -> Result<Vec<Violation>> {
+    let before = show(dir, base, LIFECYCLE_FILE);
+    let after = read_optional(&dir.join(LIFECYCLE_FILE))?;
+    let after: Vec<&str> = after.lines().map(str::trim_end).collect();
+
+    let mut cursor = 0;
+    let mut violations = Vec::new();
+    for (idx, line) in before.lines().map(str::trim_end).enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match after[cursor..].iter().position(|current| *current == line) {
+            Some(offset) => cursor += offset + 1,
+            None => violations.push(Violation {
+                file: LIFECYCLE_FILE,
+                line: idx + 1,
+                rule: Rule::LedgerRewritten,
+                message: format!(
+                    "line {} at {} was rewritten or removed; the ledger is append-only: {line}",
+                    idx + 1,
+                    short(base)
+                ),
+            }),
+        }
+    }
+    Ok(violations)
+}
+
+/// IDs newly defined since `base` that were retired before it, and new duplicate definitions.
+fn check_reuse(dir: &Path, base: &str, kind: IdKind) -> Result<Vec<Violation>> {
+    let before = defined(&show(dir, base, kind.file()), kind);
+    let mut retired = ids::history_ids(dir, kind, base);
+    retired.extend(
+        lifecycle::parse(&show(dir, base, LIFECYCLE_FILE))
+            .into_iter()
+            .map(|record| record.id)
+            .filter(|id| IdKind::of(id) == Some(kind)),
+    );
+    retired.retain(|id| !before.contains_key(id));
+
+    let mut violations = Vec::new();
+    for (id, lines) in defined(&read_optional(&dir.join(kind.file()))?, kind) {
+        if retired.contains(&id) {
+            violations.push(Violation {
+                file: kind.file(),
+                line: lines[0],
+                rule: Rule::IdReused,
+                message: format!(
+                    "{id} was retired before {} and must not be reused",
+                    short(base)
+                ),
+            });
+        }
+        // Duplicates already present at `base` are left to `schema` validation.
+        let known = before.get(&id).map_or(1, Vec::len).max(1);
+        for line in lines.iter().skip(known) {
+            violations.push(Violation {
+                file: kind.file(),
+                line: *line,
+                rule: Rule::DuplicateId,
+                message: format!(
+                    "{id} is defined more than once (first on line {})",
+                    lines[0]
+                ),
+            });
+        }
+    }
+    Ok(violations)
+}
+
+/// A clause whose TITLE moved to a different S-id.
+fn check_renumbering(dir: &Path, base: &str) -> Result<Vec<Violation>> {
+    let before = spec::parse(&show(dir, base, SPEC_FILE));
+    let after = spec::parse(&read_optional(&dir.join(SPEC_FILE))?);
+
+    let mut violations = Vec::new();
+    for old in &before.clauses {
+        let Some(title) = old.title() else {
+            continue;
+        };
+        if after.clause(&old.id).and_then(spec::SpecClause::title) == Some(title) {
+            continue;
+        }
+        for moved in after
+            .clauses
+            .iter()
+            .filter(|clause| clause.id != old.id && clause.title() == Some(title))
+        {
+            violations.push(Violation {
+                file: SPEC_FILE,
+                line: moved.line,
+                rule: Rule::ClauseRenumbered,
+                message: format!(
+                    "{} ({title}) was renumbered to {}; S-ids are never renumbered",
+                    old.id, moved.id
+                ),
+            });
+        }
+    }
+    Ok(violations)
+}
+
+/// R-ids present at `base`, now gone, without a `STATUS:removed` ledger record.
+fn check_removed_requirements(dir: &Path, base: &str, current: &BlueprintSet) -> Vec<Violation> {
+    defined(&show(dir, base, REQUIREMENTS_FILE), IdKind::Requirement)
+        .into_iter()
+        .filter(|(id, _)| !current.defines(id) && current.status_of(id) != Status::Removed)
+        .map(|(id, lines)| Violation {
+            file: REQUIREMENTS_FILE,
+            line: lines[0],
+            rule: Rule::RequirementRemoved,
+            message: format!(
+                "{id} (line {} at {}) was removed without a `STATUS:removed` record in {LIFECYCLE_FILE}",
+                lines[0],
+                short(base)
+            ),
+        })
+        .collect()
+}
+
+/// Lines on which each ID of `kind` is defined.
+fn defined(content: &str, kind: IdKind) -> BTreeMap<String, Vec<usize>> {
+    let mut lines: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, raw) in content.lines().enumerate() {
+        if let Some(id) = kind.record_id(raw.trim_end()) {
+            lines.entry(id.to_string()).or_default().push(idx + 1);
+        }
+    }
+    lines
+}
+
+/// `file` as of `rev`; empty when it did not exist yet.
+fn show(dir: &Path, rev: &str, file: &str) -> String {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .arg("show")
+        .arg(format!("{rev}:./{file}"))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default()
+}
+
+fn short(rev: &str) -> &str {
+    rev.get(..7).unwrap_or(rev)
+}
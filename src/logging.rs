@@ -1,8 +1,98 @@
+use anyhow::{Context as _, Result};
+use clap::ValueEnum;
 use nu_ansi_term::Color;
-use std::sync::OnceLock;
-use time::{OffsetDateTime, UtcOffset, macros::format_description};
+use serde_json::json;
+use std::{
+    env,
+    fmt::Write as _,
+    fs::{File, OpenOptions},
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+    process,
+    sync::{Mutex, MutexGuard, OnceLock, PoisonError},
+};
+use time::{
+    OffsetDateTime, UtcOffset, format_description::well_known::Rfc3339, macros::format_description,
+};
+
+use crate::tui;
 
 static LOCAL_OFFSET: OnceLock<UtcOffset> = OnceLock::new();
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+static RUN_ID: OnceLock<String> = OnceLock::new();
+static CONTEXT: Mutex<Context> = Mutex::new(Context {
+    crate_name: None,
+    role: None,
+    iteration: None,
+});
+
+/// Most severe first; a line is shown when its level is at or above the threshold.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+        }
+    }
+}
+
+/// Line format of `--log-file`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lower")]
+pub(crate) enum LogFormat {
+    /// The terminal lines without colors.
+    Plain,
+    /// One JSON object per line.
+    Json,
+}
+
+/// Global `--quiet`/`--verbose`/`--log-file` settings.
+pub(crate) struct LogOptions {
+    /// Terminal threshold: `Warn` for `--quiet`, `Debug` for `--verbose`.
+    pub(crate) level: Level,
+    pub(crate) file: Option<PathBuf>,
+    pub(crate) file_format: LogFormat,
+    /// Write terminal lines to stderr as JSON objects instead (for `serve` runs).
+    pub(crate) events: bool,
+}
+
+struct Settings {
+    level: Level,
+    events: bool,
+    color_stdout: bool,
+    color_stderr: bool,
+    file: Option<LogFile>,
+}
+
+struct LogFile {
+    /// `--quiet` only quiets the terminal; the file keeps every info line.
+    level: Level,
+    format: LogFormat,
+    file: Mutex<File>,
+}
+
+/// What every line carries so logs from parallel runs can be told apart.
+struct Context {
+    crate_name: Option<String>,
+    role: Option<String>,
+    iteration: Option<usize>,
+}
+
+#[derive(Copy, Clone)]
+enum Output {
+    Stdout,
+    Stderr,
+}
 
 pub fn init() {
     let offset = match UtcOffset::current_local_offset() {
@@ -18,16 +108,92 @@ pub fn init() {
     let _ = LOCAL_OFFSET.set(offset);
 }
 
+/// Apply the global logging flags; lines logged before this use the defaults.
+pub(crate) fn configure(options: LogOptions) -> Result<()> {
+    let file = options
+        .file
+        .map(|path| -> Result<LogFile> {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("failed to open log file {}", path.display()))?;
+            Ok(LogFile {
+                level: options.level.max(Level::Info),
+                format: options.file_format,
+                file: Mutex::new(file),
+            })
+        })
+        .transpose()?;
+
+    let _ = SETTINGS.set(Settings {
+        file,
+        events: options.events,
+        ..Settings::detect(options.level)
+    });
+    Ok(())
+}
+
+/// Record the crate the run works on.
+pub(crate) fn set_crate(name: &str) {
+    let mut context = context();
+    context.crate_name = Some(name.to_string());
+}
+
+/// Record which agent role is running and how many times it has run.
+pub(crate) fn set_step(role: &str, iteration: usize) {
+    let mut context = context();
+    context.role = Some(role.to_string());
+    context.iteration = Some(iteration);
+}
+
+/// `BLUEPRINTS_RUN_ID` when set (e.g. by an orchestrator), otherwise random per process.
+pub(crate) fn run_id() -> &'static str {
+    RUN_ID.get_or_init(|| {
+        env::var("BLUEPRINTS_RUN_ID")
+            .ok()
+            .filter(|id| !id.trim().is_empty())
+            .unwrap_or_else(|| {
+                let nanos = OffsetDateTime::now_utc().unix_timestamp_nanos();
+                let mixed = nanos ^ (i128::from(process::id()) << 20);
+                format!("{:08x}", mixed & 0xffff_ffff)
+            })
+    })
+}
+
 pub fn log_error(message: impl AsRef<str>) {
-    emit(Color::Red, "ERROR", message.as_ref(), Output::Stderr);
+    emit(
+        Level::Error,
+        Color::Red,
+        "ERROR",
+        message.as_ref(),
+        Output::Stderr,
+    );
+}
+
+pub fn log_warn(message: impl AsRef<str>) {
+    emit(
+        Level::Warn,
+        Color::Yellow,
+        "WARN",
+        message.as_ref(),
+        Output::Stderr,
+    );
 }
 
 pub fn log_blueprints(message: impl AsRef<str>) {
-    emit(Color::Blue, "BLUEPRINTS", message.as_ref(), Output::Stderr);
+    emit(
+        Level::Info,
+        Color::Blue,
+        "BLUEPRINTS",
+        message.as_ref(),
+        Output::Stderr,
+    );
 }
 
 pub fn log_codex(message: impl AsRef<str>) {
     emit(
+        Level::Info,
         Color::Rgb(128, 128, 128),
         "CODEX",
         message.as_ref(),
@@ -35,26 +201,111 @@ pub fn log_codex(message: impl AsRef<str>) {
     );
 }
 
-#[derive(Copy, Clone)]
-enum Output {
-    Stdout,
-    Stderr,
+pub fn log_debug(message: impl AsRef<str>) {
+    emit(
+        Level::Debug,
+        Color::DarkGray,
+        "DEBUG",
+        message.as_ref(),
+        Output::Stderr,
+    );
 }
 
-fn emit(color: Color, label: &str, message: &str, output: Output) {
-    let ts = timestamp();
-    let line = format!("[{label}][{ts}] - {message}");
-    let painted = color.paint(line);
+impl Settings {
+    fn detect(level: Level) -> Self {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self {
+            level,
+            events: false,
+            color_stdout: !no_color && io::stdout().is_terminal(),
+            color_stderr: !no_color && io::stderr().is_terminal(),
+            file: None,
+        }
+    }
+}
+
+fn emit(level: Level, color: Color, label: &str, message: &str, output: Output) {
+    let settings = SETTINGS.get_or_init(|| Settings::detect(Level::Info));
+    let to_file = settings.file.as_ref().filter(|file| level <= file.level);
+    if level > settings.level && to_file.is_none() {
+        return;
+    }
+
+    let now = OffsetDateTime::now_utc().to_offset(*LOCAL_OFFSET.get_or_init(determine_offset));
+    let context = context();
+    let line = format!(
+        "[{label}][{}][{}] - {message}",
+        timestamp(now),
+        context.describe()
+    );
+
+    let record = || {
+        json!({
+            "time": now.format(&Rfc3339).unwrap_or_default(),
+            "level": level.as_str(),
+            "label": label,
+            "run_id": run_id(),
+            "crate": context.crate_name,
+            "role": context.role,
+            "iteration": context.iteration,
+            "message": message,
+        })
+        .to_string()
+    };
 
+    if let Some(file) = to_file {
+        let entry = match file.format {
+            LogFormat::Plain => line.clone(),
+            LogFormat::Json => record(),
+        };
+        let mut handle = file.file.lock().unwrap_or_else(PoisonError::into_inner);
+        // One write per line keeps appends from parallel runs whole.
+        let _ = handle.write_all(format!("{entry}\n").as_bytes());
+    }
+    let event = (settings.events && level <= settings.level).then(record);
+    drop(context);
+
+    if let Some(event) = event {
+        eprintln!("{event}");
+        return;
+    }
+    if level > settings.level {
+        return;
+    }
+    if tui::is_active() {
+        tui::log(&line);
+        return;
+    }
     match output {
-        Output::Stdout => println!("{painted}"),
-        Output::Stderr => eprintln!("{painted}"),
+        Output::Stdout if settings.color_stdout => println!("{}", color.paint(line)),
+        Output::Stderr if settings.color_stderr => eprintln!("{}", color.paint(line)),
+        Output::Stdout => println!("{line}"),
+        Output::Stderr => eprintln!("{line}"),
     }
 }
 
-fn timestamp() -> String {
-    let offset = *LOCAL_OFFSET.get_or_init(determine_offset);
-    let now = OffsetDateTime::now_utc().to_offset(offset);
+fn context() -> MutexGuard<'static, Context> {
+    CONTEXT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Context {
+    /// `run=1a2b3c4d crate=demo role=builder iter=2`, leaving out what is not known yet.
+    fn describe(&self) -> String {
+        let mut text = format!("run={}", run_id());
+        if let Some(name) = &self.crate_name {
+            let _ = write!(text, " crate={name}");
+        }
+        if let Some(role) = &self.role {
+            let _ = write!(text, " role={}", role.replace(' ', "-"));
+        }
+        if let Some(iteration) = self.iteration {
+            let _ = write!(text, " iter={iteration}");
+        }
+        text
+    }
+}
+
+fn timestamp(now: OffsetDateTime) -> String {
     let format = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
     now.format(&format)
         .unwrap_or_else(|_| "unknown".to_string())
@@ -63,3 +314,23 @@ fn timestamp() -> String {
 fn determine_offset() -> UtcOffset {
     UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)
 }
+
+/// Drop `ESC [ ... <letter>` sequences so colored output renders as plain text.
+pub(crate) fn strip_ansi(line: &str) -> String {
+    let mut plain = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        plain.push(c);
+    }
+    plain
+}
@@ -1,17 +1,46 @@
+mod ci_report;
 mod commands;
+mod conformance;
+mod contract_check;
+mod coverage;
+mod evidence;
+mod findings;
+mod history;
 mod logging;
+mod mutants;
+mod policy;
+mod records;
+mod regressions;
+mod summarizer;
+mod tags;
+mod tui;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use commands::{
+    add::{self, AddArgs},
+    bootstrap::{self, BootstrapArgs},
+    check_history::{self, CheckHistoryArgs},
+    common::{SummarizeConfig, SummarizeMode},
     contracts::{self, ContractsArgs},
     delivery::{self, DeliveryArgs},
+    export::{self, ExportArgs},
+    id::{self, IdArgs},
     implement::{self, ImplementArgs},
+    lifecycle::{self, LifecycleArgs},
+    lint::{self, LintArgs},
+    lsp::{self, LspArgs},
+    progress::{self, ProgressArgs},
     requirements::{self, RequirementsArgs},
+    scaffold::{self, ScaffoldArgs},
+    serve::{self, ServeArgs},
     specs::{self, SpecsArgs},
     tests::{self, TestsArgs},
+    verify_plan::{self, VerifyPlanArgs},
+    watch::{self, WatchArgs},
 };
-use logging::log_error;
+use logging::{Level, LogFormat, LogOptions, log_error};
+use std::{path::PathBuf, time::Duration};
 
 #[derive(Parser)]
 #[command(
@@ -21,9 +50,58 @@ use logging::log_error;
     long_about = "TODO"
 )]
 struct Cli {
-    /// Enable live Codex output summarization
+    /// Summarize live Codex output instead of streaming it (`--summarize` alone means `llm`)
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "mode",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "llm"
+    )]
+    summarize: Option<SummarizeMode>,
+    /// Seconds of agent output collected into each summary
+    #[arg(
+        long,
+        global = true,
+        value_name = "secs",
+        default_value_t = 15,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    summarize_interval: u64,
+    /// Only the most recent bytes of each chunk are summarized
+    #[arg(
+        long,
+        global = true,
+        value_name = "bytes",
+        default_value_t = 32 * 1024,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    summarize_max_bytes: u64,
+    /// Codex profile used by `--summarize=llm`
+    #[arg(
+        long,
+        global = true,
+        value_name = "profile",
+        default_value = "summarizer"
+    )]
+    summarize_profile: String,
+    /// Only show warnings and errors on the terminal
+    #[arg(long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Also show debug lines
     #[arg(long, global = true)]
-    summarize: bool,
+    verbose: bool,
+    /// Append every log line to this file
+    #[arg(long, global = true, value_name = "path")]
+    log_file: Option<PathBuf>,
+    /// Line format of `--log-file`
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Plain)]
+    log_format: LogFormat,
+    /// Write log lines to stderr as JSON events (used by `serve` for its runs)
+    #[arg(long, global = true, hide = true)]
+    log_events: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -42,6 +120,32 @@ enum Commands {
     Delivery(DeliveryArgs),
     /// Workflow that guides translating approved blueprints into code (coming soon).
     Implement(ImplementArgs),
+    /// Append deprecations, removals and reactivations to `blueprints/06-lifecycle.md`.
+    Lifecycle(LifecycleArgs),
+    /// Allocate never-reused blueprint IDs.
+    Id(IdArgs),
+    /// Insert schema-valid requirement and spec records without an agent session.
+    Add(AddArgs),
+    /// Workflow that drafts blueprints for an existing crate from its code and tests.
+    Bootstrap(BootstrapArgs),
+    /// Generate code skeletons from blueprint records without an agent session.
+    Scaffold(ScaffoldArgs),
+    /// Report delivery plan completion per milestone, blocked items and history.
+    Progress(ProgressArgs),
+    /// Check that checked-off Delivery Plan items have tagged tests and code.
+    VerifyPlan(VerifyPlanArgs),
+    /// Check blueprint edits against git history for append-only and ID-stability rules.
+    CheckHistory(CheckHistoryArgs),
+    /// Report schema violations, traceability gaps and undefined tag IDs with stable rule IDs.
+    Lint(LintArgs),
+    /// Re-lint and re-trace blueprints whenever they or the crate sources change.
+    Watch(WatchArgs),
+    /// Serve diagnostics, navigation, hover and ID completion for blueprint files over stdio.
+    Lsp(LspArgs),
+    /// Serve workflow runs, run events, lint, trace and progress over local HTTP/JSON-RPC.
+    Serve(ServeArgs),
+    /// Export a crate's blueprints for readers who do not work with record lines.
+    Export(ExportArgs),
 }
 
 fn main() {
@@ -56,8 +160,26 @@ fn main() {
 fn run() -> Result<()> {
     let cli = Cli::parse();
 
+    logging::configure(LogOptions {
+        level: if cli.quiet {
+            Level::Warn
+        } else if cli.verbose {
+            Level::Debug
+        } else {
+            Level::Info
+        },
+        file: cli.log_file,
+        file_format: cli.log_format,
+        events: cli.log_events,
+    })?;
+
     // Configure global summarization mode (opt-in; default disabled)
-    commands::common::set_summarize_enabled(cli.summarize);
+    commands::common::set_summarize_config(SummarizeConfig {
+        mode: cli.summarize,
+        interval: Duration::from_secs(cli.summarize_interval),
+        max_chunk_bytes: usize::try_from(cli.summarize_max_bytes).unwrap_or(usize::MAX),
+        profile: cli.summarize_profile,
+    });
 
     match cli.command {
         Commands::Requirements(args) => requirements::handle(&args)?,
@@ -66,6 +188,19 @@ fn run() -> Result<()> {
         Commands::Tests(args) => tests::handle(&args)?,
         Commands::Delivery(args) => delivery::handle(&args)?,
         Commands::Implement(args) => implement::handle(&args)?,
+        Commands::Lifecycle(args) => lifecycle::handle(&args)?,
+        Commands::Id(args) => id::handle(&args)?,
+        Commands::Add(args) => add::handle(&args)?,
+        Commands::Bootstrap(args) => bootstrap::handle(&args)?,
+        Commands::Scaffold(args) => scaffold::handle(&args)?,
+        Commands::Progress(args) => progress::handle(&args)?,
+        Commands::VerifyPlan(args) => verify_plan::handle(&args)?,
+        Commands::CheckHistory(args) => check_history::handle(&args)?,
+        Commands::Lint(args) => lint::handle(&args)?,
+        Commands::Watch(args) => watch::handle(&args)?,
+        Commands::Lsp(args) => lsp::handle(&args)?,
+        Commands::Serve(args) => serve::handle(&args)?,
+        Commands::Export(args) => export::handle(&args)?,
     }
 
     Ok(())
@@ -0,0 +1,196 @@
+//! Mutation testing stage: surviving `cargo mutants` mutants grouped by the spec
+//! clause of the mutated item (via its `@s`/`@impl` tags).
+
+use anyhow::{Context, Result, anyhow};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    records::{BlueprintSet, lifecycle::Status},
+    tags::{self, TaggedSpan},
+};
+
+/// `cargo mutants --output` directory; results land in `<dir>/mutants.out/`.
+const OUTPUT_DIR: &str = "target/blueprints/mutants";
+
+/// `cargo mutants` exit codes that still produce a usable report:
+/// all caught, some missed, some timed out.
+const REPORT_EXIT_CODES: [i32; 3] = [0, 2, 3];
+
+struct Mutant {
+    description: String,
+    file: PathBuf,
+    line: usize,
+    /// `path:line:col` as printed by cargo mutants.
+    location: String,
+}
+
+pub(crate) struct MutationReport {
+    /// Score line: `caught+timeout / viable` in percent, or `n/a` without viable mutants.
+    pub(crate) summary: String,
+    /// Remaining-work lines, one per clause with survivors; empty when the score meets the threshold.
+    pub(crate) work: Vec<String>,
+}
+
+pub(crate) fn run(
+    package: &str,
+    blueprints_dir: &Path,
+    crate_dir: &Path,
+    threshold: f64,
+) -> Result<MutationReport> {
+    let output = Command::new("cargo")
+        .args(["mutants", "--package", package, "--output", OUTPUT_DIR])
+        .output()
+        .context("failed to run cargo mutants")?;
+    if !output
+        .status
+        .code()
+        .is_some_and(|code| REPORT_EXIT_CODES.contains(&code))
+    {
+        return Err(anyhow!(
+            "cargo mutants failed (is cargo-mutants installed and do the tests pass?):\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        ));
+    }
+
+    let results = Path::new(OUTPUT_DIR).join("mutants.out");
+    let caught = read_mutants(&results.join("caught.txt"), crate_dir)?.len();
+    let timeout = read_mutants(&results.join("timeout.txt"), crate_dir)?.len();
+    let missed = read_mutants(&results.join("missed.txt"), crate_dir)?;
+
+    let viable = caught + timeout + missed.len();
+    let score = (viable > 0).then(|| percent(caught + timeout, viable));
+    let summary = format!(
+        "mutation score {}: {caught} caught, {timeout} timed out, {} missed (threshold {threshold:.1}%)",
+        score.map_or_else(|| "n/a".to_string(), |s| format!("{s:.1}%")),
+        missed.len()
+    );
+
+    let work = if score.is_none_or(|score| score >= threshold) {
+        Vec::new()
+    } else {
+        let set = BlueprintSet::load(blueprints_dir)?;
+        let spans = tags::tagged_spans(crate_dir)?;
+        group_by_clause(&missed, &set, &spans)
+    };
+
+    Ok(MutationReport { summary, work })
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn percent(part: usize, whole: usize) -> f64 {
+    part as f64 * 100.0 / whole as f64
+}
+
+fn group_by_clause(missed: &[Mutant], set: &BlueprintSet, spans: &[TaggedSpan]) -> Vec<String> {
+    let mut by_clause: BTreeMap<usize, Vec<&Mutant>> = BTreeMap::new();
+    let mut untraced = Vec::new();
+
+    for mutant in missed {
+        // The innermost tagged item around the mutation decides its clauses.
+        let span = spans
+            .iter()
+            .filter(|span| span.contains(&mutant.file, mutant.line))
+            .min_by_key(|span| span.end - span.start);
+
+        let clauses: Vec<usize> = span.map_or_else(Vec::new, |span| {
+            set.spec
+                .clauses
+                .iter()
+                .enumerate()
+                .filter(|(_, clause)| set.status_of(&clause.id) != Status::Removed)
+                .filter(|(_, clause)| span.traces_to(clause))
+                .map(|(idx, _)| idx)
+                .collect()
+        });
+
+        if clauses.is_empty() {
+            untraced.push(mutant);
+        }
+        for idx in clauses {
+            by_clause.entry(idx).or_default().push(mutant);
+        }
+    }
+
+    let mut work: Vec<String> = by_clause
+        .into_iter()
+        .map(|(idx, mutants)| {
+            let clause = &set.spec.clauses[idx];
+            format!(
+                "MUTANTS: add or strengthen TV for {} ({}): {}",
+                clause.id,
+                clause.title().unwrap_or_default(),
+                describe(&mutants)
+            )
+        })
+        .collect();
+
+    if !untraced.is_empty() {
+        work.push(format!(
+            "MUTANTS: code without `@s`/`@impl` tags: {}; tag the mutated items and add or strengthen TVs for their clauses",
+            describe(&untraced)
+        ));
+    }
+
+    work
+}
+
+fn describe(mutants: &[&Mutant]) -> String {
+    let listed = mutants
+        .iter()
+        .map(|mutant| format!("mutant `{}` ({})", mutant.description, mutant.location))
+        .collect::<Vec<_>>()
+        .join("; ");
+    if mutants.len() == 1 {
+        format!("{listed} survived")
+    } else {
+        format!("{} mutants survived: {listed}", mutants.len())
+    }
+}
+
+/// Parse `path:line:col: description` lines from a cargo mutants outcome list.
+fn read_mutants(path: &Path, crate_dir: &Path) -> Result<Vec<Mutant>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let (location, description) = line.split_once(": ")?;
+            let mut parts = location.rsplitn(3, ':');
+            let _column = parts.next()?;
+            let line_no = parts.next()?.parse().ok()?;
+            let file = parts.next()?;
+            Some(Mutant {
+                description: description.trim().to_string(),
+                file: crate_relative(Path::new(file), crate_dir),
+                line: line_no,
+                location: location.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// cargo mutants prints paths relative to the workspace root it ran in.
+fn crate_relative(file: &Path, crate_dir: &Path) -> PathBuf {
+    let crate_dir = crate_dir
+        .canonicalize()
+        .unwrap_or_else(|_| crate_dir.to_path_buf());
+    env::current_dir()
+        .ok()
+        .and_then(|cwd| cwd.join(file).canonicalize().ok())
+        .and_then(|absolute| {
+            absolute
+                .strip_prefix(&crate_dir)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_else(|| file.to_path_buf())
+}
@@ -0,0 +1,299 @@
+//! Post-run file-change policy per agent role.
+//!
+//! Before each codex call the working tree (tracked and untracked, honoring
+//! `.gitignore`) is snapshotted as a git tree object through a scratch index, so
+//! neither `HEAD` nor the real index is touched. Afterwards the tree is diffed
+//! against the snapshot; changes outside the role's allowed paths are restored from
+//! the snapshot and reported to the agent in its next prompt. Rust files under
+//! tag-only paths may change only by added `///` tag lines.
+
+use anyhow::{Context, Result, anyhow};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use crate::{
+    commands::common::{CommandOutput, run_codex},
+    logging::{self, log_error},
+    tags,
+};
+
+/// Which workspace paths an agent role may change.
+pub(crate) struct Policy {
+    role: &'static str,
+    /// `None` allows every path that is not denied.
+    allow: Option<Vec<PathBuf>>,
+    deny: Vec<PathBuf>,
+    /// Rust files here that the allow list excludes may still gain `///` tag lines.
+    tags_only: Vec<PathBuf>,
+}
+
+impl Policy {
+    /// Reviewers: no file may change.
+    pub(crate) fn read_only(role: &'static str) -> Self {
+        Self {
+            role,
+            allow: Some(Vec::new()),
+            deny: Vec::new(),
+            tags_only: Vec::new(),
+        }
+    }
+
+    /// Only files at or under `paths` may change.
+    pub(crate) fn allow_only(role: &'static str, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            role,
+            allow: Some(paths.into_iter().map(|path| relative(&path)).collect()),
+            deny: Vec::new(),
+            tags_only: Vec::new(),
+        }
+    }
+
+    /// Any file may change except those at or under `paths`.
+    pub(crate) fn deny(role: &'static str, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            role,
+            allow: None,
+            deny: paths.into_iter().map(|path| relative(&path)).collect(),
+            tags_only: Vec::new(),
+        }
+    }
+
+    /// Additionally deny `paths`, e.g. files inside an allowed directory.
+    pub(crate) fn denying(mut self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.deny
+            .extend(paths.into_iter().map(|path| relative(&path)));
+        self
+    }
+
+    /// Let Rust files at or under `paths` change by added `///` tag lines only.
+    pub(crate) fn tagging(mut self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.tags_only
+            .extend(paths.into_iter().map(|path| relative(&path)));
+        self
+    }
+
+    fn permits(&self, path: &Path) -> bool {
+        let allowed = self
+            .allow
+            .as_ref()
+            .is_none_or(|allow| allow.iter().any(|prefix| path.starts_with(prefix)));
+        allowed && !self.denies(path)
+    }
+
+    fn denies(&self, path: &Path) -> bool {
+        self.deny.iter().any(|prefix| path.starts_with(prefix))
+    }
+
+    fn tags_only(&self, path: &Path) -> bool {
+        path.extension().is_some_and(|extension| extension == "rs")
+            && self.tags_only.iter().any(|prefix| path.starts_with(prefix))
+            && !self.denies(path)
+    }
+}
+
+/// Runs codex under a [`Policy`] and carries violations into the next prompt.
+pub(crate) struct Guard {
+    policy: Policy,
+    feedback: Vec<String>,
+    /// Codex runs so far; the iteration every log line of this role carries.
+    runs: usize,
+}
+
+impl Guard {
+    pub(crate) fn new(policy: Policy) -> Self {
+        Self {
+            policy,
+            feedback: Vec::new(),
+            runs: 0,
+        }
+    }
+
+    /// `run_codex`, then revert every change the policy does not permit.
+    pub(crate) fn run(&mut self, args: &[&str], prompt: &str) -> Result<CommandOutput> {
+        let prompt = if self.feedback.is_empty() {
+            prompt.to_string()
+        } else {
+            format!(
+                "{prompt}\n\nHOST FILE POLICY\nThe host reverted these changes from your previous run because the {} may not make them; do not repeat them:\n{}",
+                self.policy.role,
+                self.feedback
+                    .iter()
+                    .map(|violation| format!("- {violation}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        };
+
+        self.runs += 1;
+        logging::set_step(self.policy.role, self.runs);
+        let before = snapshot()?;
+        let output = run_codex(args, &prompt)?;
+        self.feedback = match before {
+            Some(before) => self.enforce(&before)?,
+            None => Vec::new(),
+        };
+        Ok(output)
+    }
+
+    fn enforce(&self, before: &str) -> Result<Vec<String>> {
+        let Some(after) = snapshot()? else {
+            return Ok(Vec::new());
+        };
+        if after == before {
+            return Ok(Vec::new());
+        }
+
+        let mut violations = Vec::new();
+        for (status, path) in changes(before, &after)? {
+            if self.policy.permits(&path) {
+                continue;
+            }
+            let tags_only = self.policy.tags_only(&path);
+            if tags_only && status == 'M' && only_tags_added(before, &path)? {
+                continue;
+            }
+            restore(before, status, &path)?;
+            let change = match status {
+                'A' => "added",
+                'D' => "deleted",
+                _ if tags_only => "modified beyond `///` tag lines",
+                _ => "modified",
+            };
+            let violation = format!("{} ({change})", path.display());
+            log_error(format!(
+                "{} {violation} outside its allowed paths; reverted",
+                self.policy.role
+            ));
+            violations.push(violation);
+        }
+        Ok(violations)
+    }
+}
+
+/// Paths are matched relative to the workspace root (the current directory).
+fn relative(path: &Path) -> PathBuf {
+    let path = env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf());
+    path.strip_prefix(".")
+        .map(Path::to_path_buf)
+        .unwrap_or(path)
+}
+
+/// The working tree as a git tree ID; `None` outside a git repository.
+fn snapshot() -> Result<Option<String>> {
+    let Some(real_index) = git(&["rev-parse", "--git-path", "index"]) else {
+        return Ok(None);
+    };
+    let index = env::temp_dir().join(format!("blueprints-policy-{}.index", process::id()));
+    // Seeding from the real index lets git reuse its stat cache instead of rehashing.
+    if Path::new(&real_index).exists() {
+        fs::copy(&real_index, &index).with_context(|| format!("failed to copy {real_index}"))?;
+    }
+
+    let added = Command::new("git")
+        .env("GIT_INDEX_FILE", &index)
+        .args(["add", "--all", "--", "."])
+        .output()
+        .context("failed to run git add")?;
+    let tree = if added.status.success() {
+        Command::new("git")
+            .env("GIT_INDEX_FILE", &index)
+            .arg("write-tree")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        None
+    };
+    let _ = fs::remove_file(&index);
+    Ok(tree)
+}
+
+/// `(status, path)` for every file that differs between two snapshot trees.
+fn changes(before: &str, after: &str) -> Result<Vec<(char, PathBuf)>> {
+    let diff = git(&[
+        "diff-tree",
+        "-r",
+        "--relative",
+        "--no-renames",
+        "--name-status",
+        before,
+        after,
+    ])
+    .ok_or_else(|| anyhow!("git diff-tree failed for policy snapshots"))?;
+
+    Ok(diff
+        .lines()
+        .filter_map(|line| {
+            let (status, path) = line.split_once('\t')?;
+            Some((status.chars().next()?, PathBuf::from(path)))
+        })
+        .collect())
+}
+
+/// Whether `path` differs from the `before` snapshot only by inserted `///` tag lines.
+fn only_tags_added(before: &str, path: &Path) -> Result<bool> {
+    let original = String::from_utf8_lossy(&snapshot_blob(before, path)?).into_owned();
+    let current =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+
+    let mut original = original.lines().peekable();
+    for line in current.lines() {
+        if original.peek() == Some(&line) {
+            original.next();
+        } else if !is_tag_line(line) {
+            return Ok(false);
+        }
+    }
+    Ok(original.next().is_none())
+}
+
+/// `/// @s(S-001)` and friends, on a line of their own.
+fn is_tag_line(line: &str) -> bool {
+    line.trim_start().starts_with("///") && !tags::parse_tags(line).is_empty()
+}
+
+/// Put `path` back to its state in the `before` snapshot.
+fn restore(before: &str, status: char, path: &Path) -> Result<()> {
+    if status == 'A' {
+        return fs::remove_file(path)
+            .with_context(|| format!("failed to remove {}", path.display()));
+    }
+
+    let content = snapshot_blob(before, path)?;
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(path, content).with_context(|| format!("failed to restore {}", path.display()))
+}
+
+/// Contents of `path` in a snapshot tree.
+fn snapshot_blob(tree: &str, path: &Path) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .args(["cat-file", "blob", &format!("{tree}:./{}", path.display())])
+        .output()
+        .context("failed to run git cat-file")?;
+    if !output.status.success() {
+        return Err(anyhow!("failed to read {} from snapshot", path.display()));
+    }
+    Ok(output.stdout)
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
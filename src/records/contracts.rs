@@ -0,0 +1,78 @@
+/// One `### C-### — <Short Title>` section from `03-contracts.md`.
+#[derive(Clone, Debug)]
+pub(crate) struct Contract {
+    pub(crate) id: String,
+    pub(crate) code_blocks: Vec<CodeBlock>,
+    /// 1-based line number of the heading.
+    pub(crate) line: usize,
+}
+
+/// A fenced code block inside a contract section.
+#[derive(Clone, Debug)]
+pub(crate) struct CodeBlock {
+    pub(crate) lang: String,
+    pub(crate) code: String,
+    /// 1-based line number of the first line inside the fence.
+    pub(crate) first_line: usize,
+}
+
+impl CodeBlock {
+    /// Whether the block is Rust meant to compile (`rust`, but not `rust,ignore`).
+    pub(crate) fn is_checked_rust(&self) -> bool {
+        let mut attrs = self.lang.split(',').map(str::trim);
+        attrs.next() == Some("rust") && attrs.all(|attr| attr != "ignore")
+    }
+}
+
+/// The `C-###` introduced by `line`, if it is a contract heading.
+pub(crate) fn record_id(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("### ")?;
+    let id = rest
+        .split_once(" — ")
+        .or_else(|| rest.split_once(" - "))
+        .map_or(rest, |(id, _)| id)
+        .trim();
+    (super::IdKind::of(id) == Some(super::IdKind::Contract)).then_some(id)
+}
+
+pub(crate) fn parse(content: &str) -> Vec<Contract> {
+    let mut contracts: Vec<Contract> = Vec::new();
+    let mut fence: Option<CodeBlock> = None;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = raw.trim_end();
+
+        if let Some(block) = fence.as_mut() {
+            if line.trim_start().starts_with("```") {
+                if let Some(block) = fence.take()
+                    && let Some(current) = contracts.last_mut()
+                {
+                    current.code_blocks.push(block);
+                }
+            } else {
+                block.code.push_str(line);
+                block.code.push('\n');
+            }
+            continue;
+        }
+
+        if let Some(lang) = line.trim_start().strip_prefix("```") {
+            fence = Some(CodeBlock {
+                lang: lang.trim().to_string(),
+                code: String::new(),
+                first_line: idx + 2,
+            });
+            continue;
+        }
+
+        if let Some(id) = record_id(line) {
+            contracts.push(Contract {
+                id: id.to_string(),
+                code_blocks: Vec::new(),
+                line: idx + 1,
+            });
+        }
+    }
+
+    contracts
+}
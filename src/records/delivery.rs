@@ -0,0 +1,82 @@
+use super::IdKind;
+
+/// Heading that opens a milestone section: `## Milestone: S-<id>: <Spec Title>`.
+const MILESTONE_PREFIX: &str = "## Milestone:";
+
+/// One `- [ ] DP-### <task>; Refs: ...` checklist item from `05-delivery-plan.md`.
+#[derive(Clone, Debug)]
+pub(crate) struct DeliveryItem {
+    pub(crate) id: String,
+    /// `true` for `- [x]` items.
+    pub(crate) done: bool,
+    /// Checklist text after the checkbox, including the `DP-###` and `Refs:`.
+    pub(crate) text: String,
+    /// IDs listed after `Refs:`, in order.
+    pub(crate) refs: Vec<String>,
+    /// Title of the enclosing `## Milestone:` section, if any.
+    pub(crate) milestone: Option<String>,
+    pub(crate) line: usize,
+}
+
+/// The `DP-###` defined by `line`, if it is a checklist item.
+pub(crate) fn record_id(line: &str) -> Option<&str> {
+    let (_, text) = checkbox(line)?;
+    let id = text.split_whitespace().next()?;
+    (IdKind::of(id) == Some(IdKind::DeliveryItem)).then_some(id)
+}
+
+/// The milestone title of a `## Milestone: <title>` heading.
+pub(crate) fn milestone_title(line: &str) -> Option<&str> {
+    let title = line.trim().strip_prefix(MILESTONE_PREFIX)?.trim();
+    (!title.is_empty()).then_some(title)
+}
+
+pub(crate) fn parse(content: &str) -> Vec<DeliveryItem> {
+    let mut milestone: Option<String> = None;
+    let mut items = Vec::new();
+
+    for (idx, raw) in content.lines().enumerate() {
+        if let Some(title) = milestone_title(raw) {
+            milestone = Some(title.to_string());
+            continue;
+        }
+        if raw.trim_start().starts_with("## ") {
+            milestone = None;
+            continue;
+        }
+        let (Some(id), Some((done, text))) = (record_id(raw), checkbox(raw)) else {
+            continue;
+        };
+        items.push(DeliveryItem {
+            id: id.to_string(),
+            done,
+            text: text.to_string(),
+            refs: refs(text),
+            milestone: milestone.clone(),
+            line: idx + 1,
+        });
+    }
+
+    items
+}
+
+/// `(checked, text)` of a `- [ ] text` / `- [x] text` task list entry.
+fn checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = line.trim_start().strip_prefix('-')?.trim_start();
+    let rest = rest.strip_prefix('[')?;
+    let (mark, text) = rest.split_once(']')?;
+    Some((mark.trim().eq_ignore_ascii_case("x"), text.trim()))
+}
+
+/// IDs after `Refs:`; trailing prose such as `(include only if ...)` is ignored.
+fn refs(text: &str) -> Vec<String> {
+    let Some((_, list)) = text.split_once("Refs:") else {
+        return Vec::new();
+    };
+    list.split(',')
+        .filter_map(|part| part.split_whitespace().next())
+        .map(|id| id.trim_end_matches([';', '.']))
+        .filter(|id| IdKind::of(id).is_some())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,170 @@
+//! Never-reuse ID allocation across blueprint files, the lifecycle ledger and git history.
+
+use anyhow::{Context, Result, anyhow};
+use std::{
+    collections::BTreeSet,
+    fs::{self, File, OpenOptions},
+    io::Write as _,
+    path::Path,
+    process::Command,
+};
+
+use super::{IdKind, LIFECYCLE_FILE, id_sort_key, lifecycle, read_optional};
+
+/// Advisory lock serializing allocation (and insertion) across concurrent runs;
+/// relative to the workspace root.
+const LOCK_PATH: &str = "target/blueprints/ids.lock";
+
+/// IDs handed out by `id next`, one `<blueprints dir>\t<id>` line each, so they stay
+/// used before the caller writes its record; relative to the workspace root.
+const RESERVATIONS_PATH: &str = "target/blueprints/reserved-ids";
+
+/// Held while an ID is allocated and written; released on drop.
+pub(crate) struct IdLock {
+    _file: File,
+}
+
+/// Wait until no other `blueprints` process is allocating IDs in this workspace.
+pub(crate) fn lock() -> Result<IdLock> {
+    let path = Path::new(LOCK_PATH);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path)
+        .with_context(|| format!("failed to open {LOCK_PATH}"))?;
+    file.lock()
+        .with_context(|| format!("failed to lock {LOCK_PATH}"))?;
+    Ok(IdLock { _file: file })
+}
+
+/// Mark `id` as used for `dir` even though no record defines it yet; call under [`lock`].
+pub(crate) fn reserve(dir: &Path, id: &str) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(RESERVATIONS_PATH)
+        .with_context(|| format!("failed to open {RESERVATIONS_PATH}"))?;
+    writeln!(file, "{}\t{id}", reservation_key(dir))
+        .with_context(|| format!("failed to write {RESERVATIONS_PATH}"))
+}
+
+/// IDs reserved for `dir` by earlier `id next` runs.
+fn reserved_ids(dir: &Path) -> Result<Vec<String>> {
+    let key = reservation_key(dir);
+    Ok(read_optional(Path::new(RESERVATIONS_PATH))?
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter(|(dir, _)| *dir == key)
+        .map(|(_, id)| id.to_string())
+        .collect())
+}
+
+/// The same blueprints directory may be reached by relative or absolute paths.
+fn reservation_key(dir: &Path) -> String {
+    fs::canonicalize(dir)
+        .unwrap_or_else(|_| dir.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// Every ID of `kind` that has ever been defined or reserved for this blueprints directory.
+///
+/// Sources: the owning file today, the lifecycle ledger, `id next` reservations, and
+/// every version of the owning file in git history (best-effort; skipped outside a git
+/// repository).
+pub(crate) fn used_ids(dir: &Path, kind: IdKind) -> Result<BTreeSet<String>> {
+    let mut used = BTreeSet::new();
+
+    let current = read_optional(&dir.join(kind.file()))?;
+    used.extend(
+        current
+            .lines()
+            .filter_map(|line| kind.record_id(line.trim_end()))
+            .map(str::to_string),
+    );
+
+    let ledger = lifecycle::parse(&read_optional(&dir.join(LIFECYCLE_FILE))?);
+    used.extend(
+        ledger
+            .into_iter()
+            .map(|record| record.id)
+            .filter(|id| IdKind::of(id) == Some(kind)),
+    );
+
+    used.extend(
+        reserved_ids(dir)?
+            .into_iter()
+            .filter(|id| IdKind::of(id) == Some(kind)),
+    );
+
+    used.extend(history_ids(dir, kind, "--all"));
+
+    Ok(used)
+}
+
+/// Next free top-level ID of `kind`, one past the highest ever used.
+pub(crate) fn next_id(dir: &Path, kind: IdKind) -> Result<String> {
+    let used = used_ids(dir, kind)?;
+    let highest = used.iter().map(|id| id_sort_key(id).0).max().unwrap_or(0);
+    format_id(kind, highest + 1)
+}
+
+/// Next free `S-###.n` sub-clause under `parent` (an `S-###` base ID).
+pub(crate) fn next_sub_id(dir: &Path, parent: &str) -> Result<String> {
+    if IdKind::of(parent) != Some(IdKind::Spec) || parent.contains('.') {
+        return Err(anyhow!("'{parent}' is not a base spec ID (expected S-###)"));
+    }
+
+    let used = used_ids(dir, IdKind::Spec)?;
+    if !used.contains(parent) {
+        return Err(anyhow!("{parent} has never been defined in the spec"));
+    }
+
+    let prefix = format!("{parent}.");
+    let highest = used
+        .iter()
+        .filter(|id| id.starts_with(&prefix))
+        .map(|id| id_sort_key(id).1)
+        .max()
+        .unwrap_or(0);
+    Ok(format!("{parent}.{}", highest + 1))
+}
+
+fn format_id(kind: IdKind, number: u32) -> Result<String> {
+    if number > 999 {
+        return Err(anyhow!(
+            "{}-### IDs are exhausted (next would be {number})",
+            kind.prefix()
+        ));
+    }
+    Ok(format!("{}-{number:03}", kind.prefix()))
+}
+
+/// IDs of `kind` in any version of the owning file reachable from `revs`
+/// (a revision, or `--all`); empty outside a git repository.
+pub(crate) fn history_ids(dir: &Path, kind: IdKind, revs: &str) -> BTreeSet<String> {
+    let Ok(output) = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["log", revs, "-p", "--format=", "--", kind.file()])
+        .output()
+    else {
+        return BTreeSet::new();
+    };
+
+    if !output.status.success() {
+        return BTreeSet::new();
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix(['+', '-', ' ']))
+        .filter_map(|line| kind.record_id(line.trim_end()))
+        .map(str::to_string)
+        .collect()
+}
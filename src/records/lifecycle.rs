@@ -0,0 +1,122 @@
+use super::{IdKind, split_fields};
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Status {
+    Active,
+    Deprecated,
+    Removed,
+}
+
+impl Status {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(Self::Active),
+            "deprecated" => Some(Self::Deprecated),
+            "removed" => Some(Self::Removed),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Deprecated => "deprecated",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One `<ID> | STATUS:... | REASON:...[ | EFFECTIVE:...][ | REPLACE_BY:...]` ledger entry.
+#[derive(Clone, Debug)]
+pub(crate) struct LifecycleRecord {
+    pub(crate) id: String,
+    pub(crate) status: Status,
+    pub(crate) reason: String,
+    pub(crate) effective: Option<String>,
+    pub(crate) replace_by: Option<String>,
+    pub(crate) line: usize,
+}
+
+impl LifecycleRecord {
+    pub(crate) fn render(&self) -> String {
+        let mut line = format!(
+            "{} | STATUS:{} | REASON:{}",
+            self.id, self.status, self.reason
+        );
+        if let Some(effective) = &self.effective {
+            line.push_str(" | EFFECTIVE:");
+            line.push_str(effective);
+        }
+        if let Some(replace_by) = &self.replace_by {
+            line.push_str(" | REPLACE_BY:");
+            line.push_str(replace_by);
+        }
+        line
+    }
+}
+
+/// Whether `id` may appear in the lifecycle ledger (`R`, `S`, `TV` and `C` ids only).
+pub(crate) fn is_lifecycle_id(id: &str) -> bool {
+    matches!(
+        IdKind::of(id),
+        Some(IdKind::Requirement | IdKind::Spec | IdKind::TestVector | IdKind::Contract)
+    )
+}
+
+pub(crate) fn parse(content: &str) -> Vec<LifecycleRecord> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, raw)| parse_line(raw.trim_end(), idx + 1))
+        .collect()
+}
+
+fn parse_line(line: &str, number: usize) -> Option<LifecycleRecord> {
+    let fields = split_fields(line);
+    let (&id, rest) = fields.split_first()?;
+    if !is_lifecycle_id(id) {
+        return None;
+    }
+
+    let mut status = None;
+    let mut reason = None;
+    let mut effective = None;
+    let mut replace_by = None;
+
+    for field in rest {
+        let (key, value) = field.split_once(':')?;
+        match key {
+            "STATUS" => status = Status::parse(value),
+            "REASON" => reason = Some(value.to_string()),
+            "EFFECTIVE" => effective = Some(value.to_string()),
+            "REPLACE_BY" => replace_by = Some(value.to_string()),
+            _ => return None,
+        }
+    }
+
+    Some(LifecycleRecord {
+        id: id.to_string(),
+        status: status?,
+        reason: reason?,
+        effective,
+        replace_by,
+        line: number,
+    })
+}
+
+/// Latest ledger entry for `id`; later lines supersede earlier ones.
+pub(crate) fn latest<'a>(records: &'a [LifecycleRecord], id: &str) -> Option<&'a LifecycleRecord> {
+    records.iter().rev().find(|record| record.id == id)
+}
+
+/// Effective status of `id`; IDs without ledger entries are active.
+pub(crate) fn current_status(records: &[LifecycleRecord], id: &str) -> Status {
+    latest(records, id).map_or(Status::Active, |record| record.status)
+}
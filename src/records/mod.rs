@@ -0,0 +1,227 @@
+//! Parsers for the records-only blueprint files described in `BLUEPRINTS.md`.
+//!
+//! Every parser is lenient: lines that do not match the documented schema are
+//! skipped rather than rejected, mirroring how the agent prompts treat them.
+
+pub(crate) mod contracts;
+pub(crate) mod delivery;
+pub(crate) mod ids;
+pub(crate) mod lifecycle;
+pub(crate) mod requirements;
+pub(crate) mod rules;
+pub(crate) mod schema;
+pub(crate) mod spec;
+pub(crate) mod test_vectors;
+
+use anyhow::{Context, Result, anyhow};
+use std::{fs, path::Path};
+
+pub(crate) const REQUIREMENTS_FILE: &str = "01-requirements.md";
+pub(crate) const SPEC_FILE: &str = "02-spec.md";
+pub(crate) const CONTRACTS_FILE: &str = "03-contracts.md";
+pub(crate) const TEST_VECTORS_FILE: &str = "04-test-vectors.md";
+pub(crate) const DELIVERY_PLAN_FILE: &str = "05-delivery-plan.md";
+pub(crate) const LIFECYCLE_FILE: &str = "06-lifecycle.md";
+
+/// Record separator used by every records-only file.
+pub(crate) const FIELD_SEPARATOR: &str = " | ";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum IdKind {
+    Requirement,
+    Spec,
+    Contract,
+    TestVector,
+    DeliveryItem,
+}
+
+impl IdKind {
+    pub(crate) fn prefix(self) -> &'static str {
+        match self {
+            Self::Requirement => "R",
+            Self::Spec => "S",
+            Self::Contract => "C",
+            Self::TestVector => "TV",
+            Self::DeliveryItem => "DP",
+        }
+    }
+
+    pub(crate) fn file(self) -> &'static str {
+        match self {
+            Self::Requirement => REQUIREMENTS_FILE,
+            Self::Spec => SPEC_FILE,
+            Self::Contract => CONTRACTS_FILE,
+            Self::TestVector => TEST_VECTORS_FILE,
+            Self::DeliveryItem => DELIVERY_PLAN_FILE,
+        }
+    }
+
+    /// Classify a well-formed ID (`R-###`, `S-###[.n]`, `C-###`, `TV-###`, `DP-###`).
+    pub(crate) fn of(id: &str) -> Option<Self> {
+        let (prefix, rest) = id.split_once('-')?;
+        let kind = match prefix {
+            "R" => Self::Requirement,
+            "S" => Self::Spec,
+            "C" => Self::Contract,
+            "TV" => Self::TestVector,
+            "DP" => Self::DeliveryItem,
+            _ => return None,
+        };
+
+        let (number, suffix) = match rest.split_once('.') {
+            Some((number, suffix)) if kind == Self::Spec => (number, Some(suffix)),
+            Some(_) => return None,
+            None => (rest, None),
+        };
+
+        if number.len() != 3 || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        if let Some(suffix) = suffix
+            && (suffix.is_empty() || !suffix.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return None;
+        }
+
+        Some(kind)
+    }
+
+    /// The ID of this kind that `line` defines, using the owning file's record schema.
+    pub(crate) fn record_id(self, line: &str) -> Option<&str> {
+        match self {
+            Self::Requirement => requirements::record_id(line),
+            Self::Spec => spec::record_id(line),
+            Self::Contract => contracts::record_id(line),
+            Self::TestVector => test_vectors::record_id(line),
+            Self::DeliveryItem => delivery::record_id(line),
+        }
+    }
+}
+
+/// Numeric sort key for an ID such as `S-004.2` -> `(4, 2)`.
+pub(crate) fn id_sort_key(id: &str) -> (u32, u32) {
+    let rest = id.split_once('-').map_or(id, |(_, rest)| rest);
+    let (number, suffix) = rest.split_once('.').unwrap_or((rest, "0"));
+    (number.parse().unwrap_or(0), suffix.parse().unwrap_or(0))
+}
+
+/// Split a record line into its ` | `-separated fields.
+pub(crate) fn split_fields(line: &str) -> Vec<&str> {
+    line.split(FIELD_SEPARATOR).map(str::trim).collect()
+}
+
+/// Split a comma-separated ID list such as `R-001,R-004`.
+pub(crate) fn split_ids(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Well-formed IDs on `line` with their byte offsets; `S-001` does not match inside `S-001.2`.
+pub(crate) fn id_tokens(line: &str) -> Vec<(usize, &str)> {
+    let is_id_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '.';
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (idx, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, is_id_char(c)) {
+            (None, true) => start = Some(idx),
+            (Some(begin), false) => {
+                let token = line[begin..idx].trim_end_matches('.');
+                if IdKind::of(token).is_some() {
+                    tokens.push((begin, token));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// Trim and check a free-text field value: single-line ASCII without `|` or tabs.
+pub(crate) fn validate_field(key: &str, value: &str) -> Result<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(anyhow!("{key} must not be empty"));
+    }
+    if !value.is_ascii() || value.contains(['|', '\t', '\n', '\r']) {
+        return Err(anyhow!(
+            "{key} must be single-line ASCII without '|' or tabs: {value}"
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// Read a blueprint file, treating a missing file as empty.
+pub(crate) fn read_optional(path: &Path) -> Result<String> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+/// All parsed blueprint files for a single crate or module.
+pub(crate) struct BlueprintSet {
+    pub(crate) requirements: Vec<requirements::Requirement>,
+    pub(crate) spec: spec::SpecFile,
+    pub(crate) contracts: Vec<contracts::Contract>,
+    pub(crate) test_vectors: Vec<test_vectors::TestVector>,
+    pub(crate) delivery: Vec<delivery::DeliveryItem>,
+    pub(crate) lifecycle: Vec<lifecycle::LifecycleRecord>,
+}
+
+impl BlueprintSet {
+    pub(crate) fn load(dir: &Path) -> Result<Self> {
+        Ok(Self {
+            requirements: requirements::parse(&read_optional(&dir.join(REQUIREMENTS_FILE))?),
+            spec: spec::parse(&read_optional(&dir.join(SPEC_FILE))?),
+            contracts: contracts::parse(&read_optional(&dir.join(CONTRACTS_FILE))?),
+            test_vectors: test_vectors::parse(&read_optional(&dir.join(TEST_VECTORS_FILE))?),
+            delivery: delivery::parse(&read_optional(&dir.join(DELIVERY_PLAN_FILE))?),
+            lifecycle: lifecycle::parse(&read_optional(&dir.join(LIFECYCLE_FILE))?),
+        })
+    }
+
+    /// Whether `id` is defined in the blueprint file that owns its kind.
+    pub(crate) fn defines(&self, id: &str) -> bool {
+        match IdKind::of(id) {
+            Some(IdKind::Requirement) => self.requirements.iter().any(|r| r.id == id),
+            Some(IdKind::Spec) => self.spec.clauses.iter().any(|s| s.id == id),
+            Some(IdKind::Contract) => self.contracts.iter().any(|c| c.id == id),
+            Some(IdKind::TestVector) => self.test_vectors.iter().any(|tv| tv.id == id),
+            Some(IdKind::DeliveryItem) => self.delivery.iter().any(|dp| dp.id == id),
+            None => false,
+        }
+    }
+
+    /// `<file>:<line>` where `id` is defined, if it is.
+    pub(crate) fn location_of(&self, id: &str) -> Option<String> {
+        let line = match IdKind::of(id)? {
+            IdKind::Requirement => self.requirements.iter().find(|r| r.id == id)?.line,
+            IdKind::Spec => self.spec.clauses.iter().find(|s| s.id == id)?.line,
+            IdKind::Contract => self.contracts.iter().find(|c| c.id == id)?.line,
+            IdKind::TestVector => self.test_vectors.iter().find(|tv| tv.id == id)?.line,
+            IdKind::DeliveryItem => self.delivery.iter().find(|dp| dp.id == id)?.line,
+        };
+        Some(format!("{}:{line}", IdKind::of(id)?.file()))
+    }
+
+    pub(crate) fn status_of(&self, id: &str) -> lifecycle::Status {
+        lifecycle::current_status(&self.lifecycle, id)
+    }
+}
+
+/// Insert `record` so that it becomes line `index + 1` of the file at `path`.
+pub(crate) fn insert_line(path: &Path, index: usize, record: &str) -> Result<()> {
+    let content = read_optional(path)?;
+    let mut lines: Vec<&str> = content.lines().collect();
+    lines.insert(index.min(lines.len()), record);
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    fs::write(path, updated).with_context(|| format!("failed to write {}", path.display()))
+}
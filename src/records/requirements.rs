@@ -0,0 +1,26 @@
+/// One `R-### - <sentence>` line from `01-requirements.md`.
+#[derive(Clone, Debug)]
+pub(crate) struct Requirement {
+    pub(crate) id: String,
+    /// 1-based line number within the file.
+    pub(crate) line: usize,
+}
+
+/// The `R-###` defined by `line`, if it is a requirement record.
+pub(crate) fn record_id(line: &str) -> Option<&str> {
+    let (id, _) = line.split_once(" - ")?;
+    (super::IdKind::of(id) == Some(super::IdKind::Requirement)).then_some(id)
+}
+
+pub(crate) fn parse(content: &str) -> Vec<Requirement> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, raw)| {
+            Some(Requirement {
+                id: record_id(raw.trim_end())?.to_string(),
+                line: idx + 1,
+            })
+        })
+        .collect()
+}
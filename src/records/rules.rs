@@ -0,0 +1,165 @@
+//! Stable rule IDs for schema, traceability and history findings.
+
+/// Rules in ID order. IDs are stable: new rules get the next number, retired ones are never reused.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Rule {
+    MissingTitle,
+    TitleLength,
+    MalformedRecord,
+    InvalidId,
+    DuplicateId,
+    IdOrder,
+    MissingField,
+    UnknownField,
+    FieldOrder,
+    InvalidValue,
+    UndefinedReference,
+    CoverageMismatch,
+    LineHygiene,
+    OrphanRequirement,
+    UntestedClause,
+    UnplannedTestVector,
+    UndefinedTagId,
+    LedgerRewritten,
+    IdReused,
+    ClauseRenumbered,
+    RequirementRemoved,
+    MissingFile,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Level {
+    Error,
+    Warning,
+}
+
+impl Level {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+impl Rule {
+    pub(crate) const ALL: [Self; 22] = [
+        Self::MissingTitle,
+        Self::TitleLength,
+        Self::MalformedRecord,
+        Self::InvalidId,
+        Self::DuplicateId,
+        Self::IdOrder,
+        Self::MissingField,
+        Self::UnknownField,
+        Self::FieldOrder,
+        Self::InvalidValue,
+        Self::UndefinedReference,
+        Self::CoverageMismatch,
+        Self::LineHygiene,
+        Self::OrphanRequirement,
+        Self::UntestedClause,
+        Self::UnplannedTestVector,
+        Self::UndefinedTagId,
+        Self::LedgerRewritten,
+        Self::IdReused,
+        Self::ClauseRenumbered,
+        Self::RequirementRemoved,
+        Self::MissingFile,
+    ];
+
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            Self::MissingTitle => "BP001",
+            Self::TitleLength => "BP002",
+            Self::MalformedRecord => "BP003",
+            Self::InvalidId => "BP004",
+            Self::DuplicateId => "BP005",
+            Self::IdOrder => "BP006",
+            Self::MissingField => "BP007",
+            Self::UnknownField => "BP008",
+            Self::FieldOrder => "BP009",
+            Self::InvalidValue => "BP010",
+            Self::UndefinedReference => "BP011",
+            Self::CoverageMismatch => "BP012",
+            Self::LineHygiene => "BP013",
+            Self::OrphanRequirement => "BP014",
+            Self::UntestedClause => "BP015",
+            Self::UnplannedTestVector => "BP016",
+            Self::UndefinedTagId => "BP017",
+            Self::LedgerRewritten => "BP018",
+            Self::IdReused => "BP019",
+            Self::ClauseRenumbered => "BP020",
+            Self::RequirementRemoved => "BP021",
+            Self::MissingFile => "BP022",
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::MissingTitle => "missing-title",
+            Self::TitleLength => "title-length",
+            Self::MalformedRecord => "malformed-record",
+            Self::InvalidId => "invalid-id",
+            Self::DuplicateId => "duplicate-id",
+            Self::IdOrder => "id-order",
+            Self::MissingField => "missing-field",
+            Self::UnknownField => "unknown-field",
+            Self::FieldOrder => "field-order",
+            Self::InvalidValue => "invalid-value",
+            Self::UndefinedReference => "undefined-reference",
+            Self::CoverageMismatch => "coverage-mismatch",
+            Self::LineHygiene => "line-hygiene",
+            Self::OrphanRequirement => "orphan-requirement",
+            Self::UntestedClause => "untested-clause",
+            Self::UnplannedTestVector => "unplanned-test-vector",
+            Self::UndefinedTagId => "undefined-tag-id",
+            Self::LedgerRewritten => "ledger-rewritten",
+            Self::IdReused => "id-reused",
+            Self::ClauseRenumbered => "clause-renumbered",
+            Self::RequirementRemoved => "requirement-removed",
+            Self::MissingFile => "missing-file",
+        }
+    }
+
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::MissingTitle => "Spec clause has no `TITLE:` field after `DO:`.",
+            Self::TitleLength => "Spec clause TITLE is not 4-7 words.",
+            Self::MalformedRecord => "Line does not match the record schema of its file.",
+            Self::InvalidId => "Record or reference ID is not of the expected kind.",
+            Self::DuplicateId => "ID is defined more than once.",
+            Self::IdOrder => "Record IDs are not in ascending order.",
+            Self::MissingField => "Required record field is missing or empty.",
+            Self::UnknownField => "Record has a field its schema does not define.",
+            Self::FieldOrder => "Optional fields are duplicated or out of order.",
+            Self::InvalidValue => "Field value is outside the allowed set.",
+            Self::UndefinedReference => "Reference names an ID no blueprint file defines.",
+            Self::CoverageMismatch => {
+                "Test vector references a requirement its spec clauses do not cover."
+            }
+            Self::LineHygiene => "Line has non-ASCII characters, tabs or trailing whitespace.",
+            Self::OrphanRequirement => "Active requirement is not covered by any spec clause.",
+            Self::UntestedClause => "Active spec clause has no test vector.",
+            Self::UnplannedTestVector => "Test vector is not referenced by the delivery plan.",
+            Self::UndefinedTagId => "Code tag names an ID no blueprint file defines.",
+            Self::LedgerRewritten => "Lifecycle ledger line was rewritten or removed.",
+            Self::IdReused => "Retired ID was defined again.",
+            Self::ClauseRenumbered => "Spec clause moved to a different S-id.",
+            Self::RequirementRemoved => {
+                "Requirement was removed without a `STATUS:removed` ledger record."
+            }
+            Self::MissingFile => "Blueprint file is missing or empty.",
+        }
+    }
+
+    /// Gaps in traceability are warnings; everything else breaks the schema or history rules.
+    pub(crate) fn level(self) -> Level {
+        match self {
+            Self::OrphanRequirement | Self::UntestedClause | Self::UnplannedTestVector => {
+                Level::Warning
+            }
+            _ => Level::Error,
+        }
+    }
+}
@@ -0,0 +1,516 @@
+//! Strict line-schema validation for the records-only blueprint files.
+
+use std::{collections::HashSet, fmt};
+
+use super::{
+    CONTRACTS_FILE, FIELD_SEPARATOR, IdKind, REQUIREMENTS_FILE, SPEC_FILE, TEST_VECTORS_FILE,
+    id_sort_key, requirements,
+    rules::Rule,
+    spec::{self, SpecClause},
+    split_ids,
+};
+
+const SPEC_OPTIONAL_FIELDS: [&str; 4] = ["IF", "ER", "LM", "OB"];
+const TEST_VECTOR_FIELDS: [&str; 6] = ["R", "S", "L", "GIVEN", "WHEN", "THEN"];
+const TEST_VECTOR_OPTIONAL_FIELDS: [&str; 3] = ["ERR", "DET", "OB"];
+const CONTRACT_KINDS: [&str; 3] = ["Type", "External API", "Integration Note"];
+
+#[derive(Clone, Debug)]
+pub(crate) struct Violation {
+    pub(crate) file: &'static str,
+    pub(crate) line: usize,
+    pub(crate) rule: Rule,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+struct Collector {
+    file: &'static str,
+    violations: Vec<Violation>,
+}
+
+impl Collector {
+    fn new(file: &'static str) -> Self {
+        Self {
+            file,
+            violations: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, line: usize, rule: Rule, message: impl Into<String>) {
+        self.violations.push(Violation {
+            file: self.file,
+            line,
+            rule,
+            message: message.into(),
+        });
+    }
+
+    /// Shared hygiene for records-only files: ASCII, no tabs, no trailing spaces.
+    fn hygiene(&mut self, number: usize, raw: &str) {
+        if !raw.is_ascii() {
+            self.push(
+                number,
+                Rule::LineHygiene,
+                "line contains non-ASCII characters",
+            );
+        }
+        if raw.contains('\t') {
+            self.push(number, Rule::LineHygiene, "line contains tabs");
+        }
+        if raw != raw.trim_end() {
+            self.push(number, Rule::LineHygiene, "line has trailing whitespace");
+        }
+    }
+}
+
+/// Every file schema, with cross-file references resolved against the same contents.
+pub(crate) fn check_all(
+    requirements: &str,
+    spec: &str,
+    contracts: &str,
+    test_vectors: &str,
+) -> Vec<Violation> {
+    let requirement_ids: Vec<String> = requirements::parse(requirements)
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    let clauses = spec::parse(spec).clauses;
+
+    let mut violations = check_requirements(requirements);
+    violations.extend(check_spec(spec, &requirement_ids));
+    violations.extend(check_contracts(contracts));
+    violations.extend(check_test_vectors(test_vectors, &requirement_ids, &clauses));
+    violations
+}
+
+/// `01-requirements.md`: `R-### - <one sentence ending with . ! or ?>`, unique, ascending.
+pub(crate) fn check_requirements(content: &str) -> Vec<Violation> {
+    let mut out = Collector::new(REQUIREMENTS_FILE);
+    let mut seen = HashSet::new();
+    let mut previous: Option<String> = None;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let number = idx + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+        out.hygiene(number, raw);
+
+        let Some((id, text)) = raw.split_once(" - ") else {
+            out.push(
+                number,
+                Rule::MalformedRecord,
+                "expected `R-### - <sentence>` record",
+            );
+            continue;
+        };
+        if IdKind::of(id) != Some(IdKind::Requirement) {
+            out.push(
+                number,
+                Rule::InvalidId,
+                format!("invalid requirement ID '{id}'"),
+            );
+            continue;
+        }
+        if !text.trim_end().ends_with(['.', '!', '?']) {
+            out.push(
+                number,
+                Rule::InvalidValue,
+                format!("{id} must end with '.', '!' or '?'"),
+            );
+        }
+        if !seen.insert(id.to_string()) {
+            out.push(
+                number,
+                Rule::DuplicateId,
+                format!("duplicate requirement ID {id}"),
+            );
+        }
+        if let Some(prev) = previous.as_deref()
+            && id_sort_key(prev) >= id_sort_key(id)
+        {
+            out.push(
+                number,
+                Rule::IdOrder,
+                format!("{id} is not in ascending order after {prev}"),
+            );
+        }
+        previous = Some(id.to_string());
+    }
+
+    out.violations
+}
+
+/// `02-spec.md`: S-clauses and COVERAGE lines; R references must exist in `requirement_ids`.
+pub(crate) fn check_spec(content: &str, requirement_ids: &[String]) -> Vec<Violation> {
+    let mut out = Collector::new(SPEC_FILE);
+    let mut seen = HashSet::new();
+
+    for (idx, raw) in content.lines().enumerate() {
+        let number = idx + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+        out.hygiene(number, raw);
+
+        let fields: Vec<&str> = raw.trim_end().split(FIELD_SEPARATOR).collect();
+        let head = fields[0];
+
+        if head == "COVERAGE" {
+            let valid = fields.len() == 3
+                && fields[1].starts_with("R:")
+                && fields[2]
+                    .strip_prefix("REASON:")
+                    .is_some_and(|r| !r.is_empty());
+            if !valid {
+                out.push(
+                    number,
+                    Rule::MalformedRecord,
+                    "expected `COVERAGE | R:R-###[,R-###...] | REASON:<short>`",
+                );
+            }
+            check_requirement_refs(&mut out, number, fields[1], requirement_ids);
+            continue;
+        }
+
+        if IdKind::of(head) != Some(IdKind::Spec) {
+            out.push(
+                number,
+                Rule::MalformedRecord,
+                "expected an `S-###[.n] | R:... | DO:... | TITLE:...` clause",
+            );
+            continue;
+        }
+        if !seen.insert(head.to_string()) {
+            out.push(
+                number,
+                Rule::DuplicateId,
+                format!("duplicate spec ID {head}"),
+            );
+        }
+
+        check_spec_fields(&mut out, number, head, &fields[1..], requirement_ids);
+    }
+
+    out.violations
+}
+
+fn check_spec_fields(
+    out: &mut Collector,
+    number: usize,
+    id: &str,
+    fields: &[&str],
+    requirement_ids: &[String],
+) {
+    match fields.first() {
+        Some(refs) if refs.starts_with("R:") => {
+            check_requirement_refs(out, number, refs, requirement_ids);
+        }
+        _ => out.push(
+            number,
+            Rule::MissingField,
+            format!("{id} must have `R:` as its second field"),
+        ),
+    }
+
+    if fields
+        .get(1)
+        .and_then(|f| f.strip_prefix("DO:"))
+        .is_none_or(|v| v.trim().is_empty())
+    {
+        out.push(
+            number,
+            Rule::MissingField,
+            format!("{id} must have a non-empty `DO:` third field"),
+        );
+    }
+
+    match fields.get(2).and_then(|f| f.strip_prefix("TITLE:")) {
+        Some(title) => {
+            let words = title.split_whitespace().count();
+            if !(4..=7).contains(&words) {
+                out.push(
+                    number,
+                    Rule::TitleLength,
+                    format!("{id} TITLE must be 4-7 words (got {words})"),
+                );
+            }
+        }
+        None => out.push(
+            number,
+            Rule::MissingTitle,
+            format!("{id} is missing `TITLE:` immediately after `DO:`"),
+        ),
+    }
+
+    let mut last_optional = None;
+    for field in fields.iter().skip(3) {
+        let key = field.split_once(':').map_or(*field, |(key, _)| key);
+        match SPEC_OPTIONAL_FIELDS.iter().position(|k| *k == key) {
+            Some(position) if last_optional.is_none_or(|last| position > last) => {
+                last_optional = Some(position);
+            }
+            Some(_) => out.push(
+                number,
+                Rule::FieldOrder,
+                format!("{id} optional field {key} is duplicated or out of IF/ER/LM/OB order"),
+            ),
+            None => out.push(
+                number,
+                Rule::UnknownField,
+                format!("{id} has unknown field `{key}`"),
+            ),
+        }
+    }
+}
+
+fn check_requirement_refs(
+    out: &mut Collector,
+    number: usize,
+    field: &str,
+    requirement_ids: &[String],
+) {
+    let Some(refs) = field.strip_prefix("R:") else {
+        return;
+    };
+    let ids = split_ids(refs);
+    if ids.is_empty() {
+        out.push(
+            number,
+            Rule::MissingField,
+            "`R:` must list at least one requirement",
+        );
+    }
+    for id in ids {
+        if IdKind::of(&id) != Some(IdKind::Requirement) {
+            out.push(
+                number,
+                Rule::InvalidId,
+                format!("invalid requirement reference '{id}'"),
+            );
+        } else if !requirement_ids.contains(&id) {
+            out.push(
+                number,
+                Rule::UndefinedReference,
+                format!("{id} is not defined in {REQUIREMENTS_FILE}"),
+            );
+        }
+    }
+}
+
+/// `04-test-vectors.md`: TV records whose `S:`/`R:` references exist, with each `R:`
+/// list a subset of every referenced clause's coverage.
+pub(crate) fn check_test_vectors(
+    content: &str,
+    requirement_ids: &[String],
+    clauses: &[SpecClause],
+) -> Vec<Violation> {
+    let mut out = Collector::new(TEST_VECTORS_FILE);
+    let mut seen = HashSet::new();
+    let mut previous: Option<String> = None;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let number = idx + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+        out.hygiene(number, raw);
+
+        let fields: Vec<&str> = raw.trim_end().split(FIELD_SEPARATOR).collect();
+        let id = fields[0];
+        if IdKind::of(id) != Some(IdKind::TestVector) {
+            out.push(
+                number, Rule::MalformedRecord,
+                "expected a `TV-### | R:... | S:... | L:... | GIVEN:... | WHEN:... | THEN:...` record",
+            );
+            continue;
+        }
+        if !seen.insert(id.to_string()) {
+            out.push(
+                number,
+                Rule::DuplicateId,
+                format!("duplicate test vector ID {id}"),
+            );
+        }
+        if let Some(prev) = previous.as_deref()
+            && id_sort_key(prev) >= id_sort_key(id)
+        {
+            out.push(
+                number,
+                Rule::IdOrder,
+                format!("{id} is not in ascending order after {prev}"),
+            );
+        }
+        previous = Some(id.to_string());
+
+        let values: Vec<Option<&str>> = TEST_VECTOR_FIELDS
+            .iter()
+            .enumerate()
+            .map(|(position, key)| {
+                fields
+                    .get(position + 1)
+                    .and_then(|field| field.strip_prefix(key))
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    .filter(|value| !value.trim().is_empty())
+            })
+            .collect();
+        if let Some(missing) = values.iter().position(Option::is_none) {
+            out.push(
+                number,
+                Rule::MissingField,
+                format!(
+                    "{id} field {} must be `{}:<value>`",
+                    missing + 2,
+                    TEST_VECTOR_FIELDS[missing]
+                ),
+            );
+            continue;
+        }
+
+        for field in fields.iter().skip(TEST_VECTOR_FIELDS.len() + 1) {
+            let key = field.split_once(':').map_or(*field, |(key, _)| key);
+            if !TEST_VECTOR_OPTIONAL_FIELDS.contains(&key) {
+                out.push(
+                    number,
+                    Rule::UnknownField,
+                    format!("{id} has unknown field `{key}`"),
+                );
+            }
+        }
+
+        let levels = values[2].unwrap_or_default();
+        if !levels
+            .split(',')
+            .all(|level| matches!(level, "U" | "I" | "P"))
+        {
+            out.push(
+                number,
+                Rule::InvalidValue,
+                format!("{id} L must list U, I or P codes"),
+            );
+        }
+
+        let refs = split_ids(values[0].unwrap_or_default());
+        check_requirement_refs(&mut out, number, fields[1], requirement_ids);
+        for spec_id in split_ids(values[1].unwrap_or_default()) {
+            let Some(clause) = clauses.iter().find(|clause| clause.id == spec_id) else {
+                out.push(
+                    number,
+                    Rule::UndefinedReference,
+                    format!("{spec_id} is not defined in {SPEC_FILE}"),
+                );
+                continue;
+            };
+            for requirement in refs.iter().filter(|r| !clause.requirements.contains(r)) {
+                out.push(
+                    number,
+                    Rule::CoverageMismatch,
+                    format!("{id} references {requirement}, which {spec_id} does not cover"),
+                );
+            }
+        }
+    }
+
+    out.violations
+}
+
+/// `03-contracts.md`: unique ascending `### C-### — <Title>` headings, each with a `Kind:`.
+pub(crate) fn check_contracts(content: &str) -> Vec<Violation> {
+    let mut out = Collector::new(CONTRACTS_FILE);
+    let mut seen = HashSet::new();
+    let mut previous: Option<String> = None;
+    // (heading line, id, kind seen)
+    let mut open: Option<(usize, String, bool)> = None;
+    let mut in_fence = false;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let number = idx + 1;
+        let line = raw.trim_end();
+
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        if line.starts_with("### C-") {
+            if let Some((at, id, false)) = open.take() {
+                out.push(
+                    at,
+                    Rule::MissingField,
+                    format!("{id} is missing a `Kind:` line"),
+                );
+            }
+
+            let Some((id, title)) = line[4..].split_once(" — ") else {
+                out.push(
+                    number,
+                    Rule::MalformedRecord,
+                    "expected `### C-### — <Short Title>` heading",
+                );
+                continue;
+            };
+            if IdKind::of(id) != Some(IdKind::Contract) || title.trim().is_empty() {
+                out.push(
+                    number,
+                    Rule::MalformedRecord,
+                    "expected `### C-### — <Short Title>` heading",
+                );
+                continue;
+            }
+            if !seen.insert(id.to_string()) {
+                out.push(
+                    number,
+                    Rule::DuplicateId,
+                    format!("duplicate contract ID {id}"),
+                );
+            }
+            if let Some(prev) = previous.as_deref()
+                && id_sort_key(prev) >= id_sort_key(id)
+            {
+                out.push(
+                    number,
+                    Rule::IdOrder,
+                    format!("{id} is not in ascending order after {prev}"),
+                );
+            }
+            previous = Some(id.to_string());
+            open = Some((number, id.to_string(), false));
+            continue;
+        }
+
+        if let Some((_, id, kind_seen)) = open.as_mut()
+            && let Some(kind) = line
+                .trim_start_matches(['-', '*', ' '])
+                .strip_prefix("Kind:")
+        {
+            let kind = kind.trim().trim_matches('`');
+            if !CONTRACT_KINDS.contains(&kind) {
+                out.push(
+                    number,
+                    Rule::InvalidValue,
+                    format!("{id} Kind must be one of Type, External API, Integration Note"),
+                );
+            }
+            *kind_seen = true;
+        }
+    }
+
+    if let Some((at, id, false)) = open {
+        out.push(
+            at,
+            Rule::MissingField,
+            format!("{id} is missing a `Kind:` line"),
+        );
+    }
+
+    out.violations
+}
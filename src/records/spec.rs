@@ -0,0 +1,66 @@
+use super::{IdKind, split_fields, split_ids};
+
+/// One `S-###[.n] | R:... | DO:... | TITLE:...` clause from `02-spec.md`.
+#[derive(Clone, Debug)]
+pub(crate) struct SpecClause {
+    pub(crate) id: String,
+    pub(crate) requirements: Vec<String>,
+    /// Remaining `KEY:value` fields in file order (`DO`, `TITLE`, `IF`, `ER`, `LM`, `OB`).
+    pub(crate) fields: Vec<(String, String)>,
+    pub(crate) line: usize,
+}
+
+impl SpecClause {
+    pub(crate) fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub(crate) fn title(&self) -> Option<&str> {
+        self.field("TITLE")
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SpecFile {
+    /// Clauses in file order, which is authoritative for S-ids.
+    pub(crate) clauses: Vec<SpecClause>,
+}
+
+impl SpecFile {
+    pub(crate) fn clause(&self, id: &str) -> Option<&SpecClause> {
+        self.clauses.iter().find(|clause| clause.id == id)
+    }
+}
+
+/// The `S-###[.n]` defined by `line`, if it is a spec clause.
+pub(crate) fn record_id(line: &str) -> Option<&str> {
+    let (id, rest) = line.split_once(super::FIELD_SEPARATOR)?;
+    (IdKind::of(id) == Some(IdKind::Spec) && rest.starts_with("R:")).then_some(id)
+}
+
+pub(crate) fn parse(content: &str) -> SpecFile {
+    let clauses = content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, raw)| {
+            let id = record_id(raw.trim_end())?;
+            let fields = split_fields(raw.trim_end());
+            let refs = fields[1].strip_prefix("R:")?;
+            Some(SpecClause {
+                id: id.to_string(),
+                requirements: split_ids(refs),
+                fields: fields[2..]
+                    .iter()
+                    .filter_map(|field| field.split_once(':'))
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+                line: idx + 1,
+            })
+        })
+        .collect();
+
+    SpecFile { clauses }
+}
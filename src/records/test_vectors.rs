@@ -0,0 +1,85 @@
+use super::{IdKind, split_fields, split_ids};
+
+/// One `TV-### | R:... | S:... | L:... | GIVEN:... | WHEN:... | THEN:...` line.
+#[derive(Clone, Debug)]
+pub(crate) struct TestVector {
+    pub(crate) id: String,
+    pub(crate) requirements: Vec<String>,
+    pub(crate) specs: Vec<String>,
+    /// Level codes: `U` (unit), `I` (integration), `P` (property).
+    pub(crate) levels: Vec<char>,
+    pub(crate) given: String,
+    pub(crate) when: String,
+    pub(crate) then: String,
+    /// Optional trailing `ERR`/`DET`/`OB` fields in file order.
+    pub(crate) extra: Vec<(String, String)>,
+    pub(crate) line: usize,
+}
+
+impl TestVector {
+    pub(crate) fn has_level(&self, level: char) -> bool {
+        self.levels.contains(&level)
+    }
+}
+
+/// The `TV-###` defined by `line`, if it is a test vector record.
+pub(crate) fn record_id(line: &str) -> Option<&str> {
+    let (id, _) = line.split_once(super::FIELD_SEPARATOR)?;
+    (IdKind::of(id) == Some(IdKind::TestVector)).then_some(id)
+}
+
+pub(crate) fn parse(content: &str) -> Vec<TestVector> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, raw)| parse_line(raw.trim_end(), idx + 1))
+        .collect()
+}
+
+fn parse_line(line: &str, number: usize) -> Option<TestVector> {
+    let fields = split_fields(line);
+    let (&id, rest) = fields.split_first()?;
+    if IdKind::of(id) != Some(IdKind::TestVector) {
+        return None;
+    }
+
+    let mut requirements = None;
+    let mut specs = None;
+    let mut levels = None;
+    let mut given = None;
+    let mut when = None;
+    let mut then = None;
+    let mut extra = Vec::new();
+
+    for field in rest {
+        let (key, value) = field.split_once(':')?;
+        match key {
+            "R" => requirements = Some(split_ids(value)),
+            "S" => specs = Some(split_ids(value)),
+            "L" => {
+                levels = Some(
+                    value
+                        .split(',')
+                        .filter_map(|code| code.trim().chars().next())
+                        .collect(),
+                );
+            }
+            "GIVEN" => given = Some(value.to_string()),
+            "WHEN" => when = Some(value.to_string()),
+            "THEN" => then = Some(value.to_string()),
+            _ => extra.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    Some(TestVector {
+        id: id.to_string(),
+        requirements: requirements?,
+        specs: specs?,
+        levels: levels?,
+        given: given?,
+        when: when?,
+        then: then?,
+        extra,
+        line: number,
+    })
+}
@@ -0,0 +1,351 @@
+//! Regression test vectors for nextest failures that the CI fixer resolved.
+//!
+//! A fixed test is traced back to blueprints through its `@tv(TV-...)`, `@s(S-...)`
+//! and `@covers(R-...)` tags; the proposed `TV-###` record references the same
+//! clauses so the bug stays covered by a vector of its own. GIVEN/WHEN/THEN come
+//! from the test's `@tv` vector; without one the record is left for a human to fill in.
+
+use anyhow::{Result, anyhow};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use crate::{
+    records::{
+        BlueprintSet, IdKind, TEST_VECTORS_FILE, id_sort_key, ids, insert_line, lifecycle::Status,
+        read_optional, schema, test_vectors::TestVector,
+    },
+    tags::{self, Tag, TagKind},
+};
+
+/// Longest failure message carried into a `THEN:` field.
+const MAX_MESSAGE_LEN: usize = 120;
+
+/// A test reported as `FAIL` by `cargo nextest run`.
+#[derive(Clone, Debug)]
+pub(crate) struct FailedTest {
+    /// nextest binary ID, e.g. `demo` or `demo::test_vectors`.
+    pub(crate) binary: String,
+    /// Test path within the binary, e.g. `tests::tv_001_add`.
+    pub(crate) path: String,
+    /// First line of the panic message, if nextest printed one.
+    pub(crate) message: Option<String>,
+}
+
+impl FailedTest {
+    fn name(&self) -> &str {
+        self.path.rsplit("::").next().unwrap_or(&self.path)
+    }
+
+    /// Integration test binaries are `<package>::<target>`; unit tests are `<package>`.
+    fn is_integration(&self) -> bool {
+        self.binary
+            .split_once("::")
+            .is_some_and(|(_, target)| !target.starts_with("bin/"))
+    }
+}
+
+/// Merge `failed` into `seen`, keeping the first message recorded for each test.
+pub(crate) fn merge(seen: &mut Vec<FailedTest>, failed: Vec<FailedTest>) {
+    for test in failed {
+        if !seen
+            .iter()
+            .any(|known| known.binary == test.binary && known.path == test.path)
+        {
+            seen.push(test);
+        }
+    }
+}
+
+/// Failed tests from `cargo nextest run` output (`FAIL [ 0.004s] demo tests::name`).
+pub(crate) fn parse_nextest_failures(output: &str) -> Vec<FailedTest> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut failed = Vec::new();
+
+    for line in &lines {
+        let trimmed = line.trim_start();
+        // Retried tests are reported as `TRY 2 FAIL [...]`.
+        let Some((prefix, rest)) = trimmed.split_once("FAIL [") else {
+            continue;
+        };
+        if !(prefix.is_empty() || prefix.starts_with("TRY ")) {
+            continue;
+        }
+        let Some((_, rest)) = rest.split_once(']') else {
+            continue;
+        };
+        let mut words = rest
+            .split_whitespace()
+            .skip_while(|word| word.starts_with('('));
+        let (Some(binary), Some(path)) = (words.next(), words.next()) else {
+            continue;
+        };
+
+        let test = FailedTest {
+            binary: binary.to_string(),
+            path: path.to_string(),
+            message: panic_message(&lines, path),
+        };
+        merge(&mut failed, vec![test]);
+    }
+
+    failed
+}
+
+/// The line after `thread '<path>' panicked at <location>:`.
+fn panic_message(lines: &[&str], path: &str) -> Option<String> {
+    let marker = format!("thread '{path}' panicked at");
+    let idx = lines.iter().position(|line| line.contains(&marker))?;
+    lines
+        .get(idx + 1)
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// A regression vector proposed for one fixed test.
+pub(crate) struct Proposal {
+    pub(crate) test: String,
+    /// The `TV-###` record, or why none could be derived.
+    pub(crate) record: Result<String, String>,
+}
+
+/// Propose one `TV-###` record per fixed test, numbered from the next free TV ID.
+pub(crate) fn propose(
+    blueprints_dir: &Path,
+    crate_dir: &Path,
+    fixed: &[FailedTest],
+) -> Result<Vec<Proposal>> {
+    let set = BlueprintSet::load(blueprints_dir)?;
+    let tags = tags::scan_crate(crate_dir)?;
+    let mut next = id_sort_key(&ids::next_id(blueprints_dir, IdKind::TestVector)?).0;
+
+    let mut proposals = Vec::new();
+    for test in fixed {
+        let record = if set
+            .test_vectors
+            .iter()
+            .any(|tv| tv.then.contains(&regression_marker(test)))
+        {
+            Err("a regression vector already exists".to_string())
+        } else if next > 999 {
+            Err("TV-### IDs are exhausted".to_string())
+        } else {
+            record(&set, &tags, test, &format!("TV-{next:03}"))
+        };
+        if record.is_ok() {
+            next += 1;
+        }
+        proposals.push(Proposal {
+            test: format!("{} {}", test.binary, test.path),
+            record,
+        });
+    }
+
+    Ok(proposals)
+}
+
+fn record(set: &BlueprintSet, tags: &[Tag], test: &FailedTest, id: &str) -> Result<String, String> {
+    let test_tags = test_tags(tags, test);
+    if test_tags.is_empty() {
+        return Err(format!(
+            "no `@tv`/`@s` tags on test fn `{}` (or it no longer exists)",
+            test.name()
+        ));
+    }
+
+    let vectors: Vec<&TestVector> = tagged_ids(&test_tags, TagKind::TestVector)
+        .iter()
+        .filter_map(|id| set.test_vectors.iter().find(|tv| &tv.id == id))
+        .collect();
+
+    let mut specs = tagged_ids(&test_tags, TagKind::Spec);
+    specs.extend(vectors.iter().flat_map(|tv| tv.specs.iter().cloned()));
+    let mut specs: Vec<String> = specs
+        .into_iter()
+        .filter(|id| set.defines(id) && set.status_of(id) != Status::Removed)
+        .collect();
+    specs.sort_by_key(|id| id_sort_key(id));
+    specs.dedup();
+    if specs.is_empty() {
+        return Err("its `@tv`/`@s` tags reference no active spec clause".to_string());
+    }
+
+    let mut candidates = tagged_ids(&test_tags, TagKind::Covers);
+    candidates.extend(
+        vectors
+            .iter()
+            .flat_map(|tv| tv.requirements.iter().cloned()),
+    );
+    let requirements = requirements_for(set, &mut specs, &candidates);
+    if requirements.is_empty() {
+        return Err(format!("{} cover no requirement", specs.join(",")));
+    }
+
+    let mut levels: BTreeSet<char> = vectors.iter().flat_map(|tv| tv.levels.clone()).collect();
+    if levels.is_empty() {
+        levels.insert(if test.is_integration() { 'I' } else { 'U' });
+    }
+    let levels: Vec<String> = ['U', 'I', 'P']
+        .into_iter()
+        .filter(|level| levels.contains(level))
+        .map(String::from)
+        .collect();
+
+    let references = format!(
+        "R:{} | S:{} | L:{}",
+        requirements.join(","),
+        specs.join(","),
+        levels.join(",")
+    );
+    let failure = test.message.as_deref().map_or_else(String::new, |message| {
+        format!(" (failed with: {})", sanitize(&truncate(message)))
+    });
+
+    // Inputs and expected outputs must be concrete; only a linked vector has them.
+    let [tv] = vectors.as_slice() else {
+        return Err(format!(
+            "no single `@tv` vector to take GIVEN/WHEN/THEN from; fill in the inputs and expected output, then add it with an ID from `blueprints id next tv`: \
+             TV-### | {references} | GIVEN:<inputs> | WHEN:<action> | THEN:<expected output>; {}{failure}",
+            regression_marker(test)
+        ));
+    };
+    Ok(format!(
+        "{id} | {references} | GIVEN:{} | WHEN:{} | THEN:{}; {}{failure}",
+        sanitize(&tv.given),
+        sanitize(&tv.when),
+        sanitize(&tv.then),
+        regression_marker(test)
+    ))
+}
+
+/// Tags on the test fn; integration tests live under `tests/`, unit tests under `src/`.
+fn test_tags<'a>(tags: &'a [Tag], test: &FailedTest) -> Vec<&'a Tag> {
+    let named: Vec<&Tag> = tags
+        .iter()
+        .filter(|tag| {
+            tag.item
+                .as_ref()
+                .is_some_and(|item| item.is_test && item.name == test.name())
+        })
+        .collect();
+    let in_target: Vec<&Tag> = named
+        .iter()
+        .copied()
+        .filter(|tag| tag.file.starts_with("tests") == test.is_integration())
+        .collect();
+    if in_target.is_empty() {
+        named
+    } else {
+        in_target
+    }
+}
+
+fn tagged_ids(tags: &[&Tag], kind: TagKind) -> Vec<String> {
+    let ids: BTreeSet<&String> = tags
+        .iter()
+        .filter(|tag| tag.kind == kind)
+        .flat_map(|tag| tag.ids.iter())
+        .collect();
+    ids.into_iter().cloned().collect()
+}
+
+/// Requirements every clause in `specs` covers, preferring the tagged `candidates`.
+///
+/// When the clauses share no requirement, only the first clause is kept so the
+/// record still satisfies the `R:` ⊆ clause coverage rule.
+fn requirements_for(
+    set: &BlueprintSet,
+    specs: &mut Vec<String>,
+    candidates: &[String],
+) -> Vec<String> {
+    let coverage = |specs: &[String]| -> Vec<String> {
+        let mut counts: BTreeMap<&String, usize> = BTreeMap::new();
+        for clause in set.spec.clauses.iter().filter(|c| specs.contains(&c.id)) {
+            for requirement in &clause.requirements {
+                *counts.entry(requirement).or_default() += 1;
+            }
+        }
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count == specs.len())
+            .map(|(requirement, _)| requirement.clone())
+            .collect()
+    };
+
+    let mut common = coverage(specs);
+    if common.is_empty() {
+        specs.truncate(1);
+        common = coverage(specs);
+    }
+
+    let preferred: Vec<String> = common
+        .iter()
+        .filter(|requirement| candidates.contains(requirement))
+        .cloned()
+        .collect();
+    let mut requirements = if preferred.is_empty() {
+        common
+    } else {
+        preferred
+    };
+    requirements.sort_by_key(|id| id_sort_key(id));
+    requirements
+}
+
+/// Appended to `THEN:` so the same fix is never captured twice.
+fn regression_marker(test: &FailedTest) -> String {
+    format!("regression for CI failure of `{}`", test.path)
+}
+
+fn truncate(message: &str) -> String {
+    if message.chars().count() <= MAX_MESSAGE_LEN {
+        return message.to_string();
+    }
+    let mut short: String = message.chars().take(MAX_MESSAGE_LEN - 3).collect();
+    short.push_str("...");
+    short
+}
+
+/// Records-only hygiene for free text: ASCII, no tabs, no field separators.
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '|' => '/',
+            '\t' => ' ',
+            c if c.is_ascii() => c,
+            _ => '?',
+        })
+        .collect()
+}
+
+/// Append `record` to `04-test-vectors.md` if the file still passes schema validation.
+pub(crate) fn append(blueprints_dir: &Path, record: &str) -> Result<()> {
+    let path = blueprints_dir.join(TEST_VECTORS_FILE);
+    let set = BlueprintSet::load(blueprints_dir)?;
+    let current = read_optional(&path)?;
+
+    let mut updated = current.trim_end().to_string();
+    if !updated.is_empty() {
+        updated.push('\n');
+    }
+    updated.push_str(record);
+    let new_line = updated.lines().count();
+
+    let requirement_ids: Vec<String> = set.requirements.iter().map(|r| r.id.clone()).collect();
+    let violations: Vec<String> =
+        schema::check_test_vectors(&updated, &requirement_ids, &set.spec.clauses)
+            .into_iter()
+            .filter(|violation| violation.line == new_line)
+            .map(|violation| violation.message)
+            .collect();
+    if !violations.is_empty() {
+        return Err(anyhow!(
+            "regression vector failed schema validation: {}",
+            violations.join("; ")
+        ));
+    }
+
+    insert_line(&path, usize::MAX, record)
+}
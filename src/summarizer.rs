@@ -0,0 +1,220 @@
+//! Rule-based `--summarize=heuristic` progress lines: the commands the agent ran,
+//! the files it edited, test and compile outcomes and control tokens, read
+//! straight from `codex exec` output without another model call.
+
+use std::fmt::Write as _;
+
+use crate::logging::strip_ansi;
+
+/// Commands and files listed by name before the rest are counted.
+const MAX_LISTED: usize = 3;
+
+/// Longest command or fallback line quoted in a summary.
+const MAX_QUOTE_LEN: usize = 60;
+
+#[derive(Default)]
+struct Activity {
+    commands: Vec<String>,
+    failed_commands: usize,
+    files: Vec<String>,
+    tests_passed: usize,
+    tests_failed: usize,
+    compile_errors: usize,
+    warnings: usize,
+    tokens: Vec<&'static str>,
+    /// Last line that is none of the above, shown when nothing else was recognized.
+    last_message: Option<String>,
+}
+
+/// One progress line for `chunk`; `control_tokens` are the loop's COMPLETED,
+/// CONTINUE and ERROR tokens.
+pub(crate) fn summarize(chunk: &str, control_tokens: &[&'static str]) -> String {
+    let mut activity = Activity::default();
+    for raw in chunk.lines() {
+        let plain = strip_ansi(raw);
+        let line = plain.trim().trim_start_matches("[stderr] ").trim_start();
+        if line.is_empty() {
+            continue;
+        }
+        activity.read(line, control_tokens);
+    }
+    activity.line()
+}
+
+impl Activity {
+    fn read(&mut self, line: &str, control_tokens: &[&'static str]) {
+        // Older `codex exec` prefixes event lines with `[timestamp] `.
+        let event = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once("] "))
+            .map_or(line, |(_, event)| event);
+
+        if let Some(token) = control_tokens.iter().find(|token| event == **token) {
+            if !self.tokens.contains(token) {
+                self.tokens.push(token);
+            }
+        } else if let Some(command) = shell_command(event) {
+            if !self.commands.contains(&command) {
+                self.commands.push(command);
+            }
+        } else if is_failed_command(event) {
+            self.failed_commands += 1;
+        } else if let Some(file) = edited_file(event) {
+            if !self.files.contains(&file) {
+                self.files.push(file);
+            }
+        } else if let Some((passed, failed)) = test_counts(event) {
+            self.tests_passed += passed;
+            self.tests_failed += failed;
+        } else if event.starts_with("error[E") || event.starts_with("error: could not compile") {
+            self.compile_errors += 1;
+        } else if event.starts_with("warning: ") && !event.contains("generated") {
+            self.warnings += 1;
+        } else if !is_noise(event) {
+            self.last_message = Some(event.to_string());
+        }
+    }
+
+    fn line(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.commands.is_empty() {
+            let mut part = format!(
+                "ran {} ({})",
+                plural(self.commands.len(), "command"),
+                listed(&self.commands)
+            );
+            if self.failed_commands > 0 {
+                let _ = write!(part, ", {} failed", self.failed_commands);
+            }
+            parts.push(part);
+        }
+        if !self.files.is_empty() {
+            parts.push(format!("edited {}", listed(&self.files)));
+        }
+        if self.tests_passed + self.tests_failed > 0 {
+            parts.push(format!(
+                "tests {} passed, {} failed",
+                self.tests_passed, self.tests_failed
+            ));
+        }
+        if self.compile_errors > 0 {
+            parts.push(plural(self.compile_errors, "compile error"));
+        }
+        if self.warnings > 0 {
+            parts.push(plural(self.warnings, "warning"));
+        }
+        if !self.tokens.is_empty() {
+            parts.push(format!("signalled {}", self.tokens.join(", ")));
+        }
+
+        if parts.is_empty() {
+            return self
+                .last_message
+                .as_deref()
+                .map_or_else(String::new, |message| format!("Agent: {}", quote(message)));
+        }
+        let mut line = parts.join("; ");
+        if let Some(first) = line.get(..1) {
+            line.replace_range(..1, &first.to_uppercase());
+        }
+        line
+    }
+}
+
+/// `bash -lc 'cargo test' in /repo` and `exec bash -lc ...` command lines.
+fn shell_command(event: &str) -> Option<String> {
+    let (_, rest) = event.split_once(" -lc ")?;
+    if event.contains(" succeeded in ") || event.contains(" exited ") {
+        return None;
+    }
+    let rest = rest
+        .rsplit_once(" in ")
+        .map_or(rest, |(command, _)| command);
+    let command = rest.trim().trim_matches(|c| c == '\'' || c == '"').trim();
+    (!command.is_empty()).then(|| quote(command))
+}
+
+/// `bash -lc '...' exited 101 in 2.3s:`; `apply_patch` results are reported through their files.
+fn is_failed_command(event: &str) -> bool {
+    event.contains(" exited ") && !event.contains(" exited 0 ") && !event.starts_with("apply_patch")
+}
+
+/// `M src/lib.rs`, `A tests/new.rs` and `D old.rs` lines of patch results.
+fn edited_file(event: &str) -> Option<String> {
+    let (status, path) = event.split_once(' ')?;
+    if !matches!(status, "M" | "A" | "D") || path.contains(' ') {
+        return None;
+    }
+    let looks_like_path = path.contains('/') || path.contains('.');
+    looks_like_path.then(|| path.to_string())
+}
+
+/// `test result: ok. 3 passed; 1 failed; ...` from cargo test and
+/// `Summary [ 0.010s] 3 tests run: 2 passed, 1 failed` from nextest.
+fn test_counts(event: &str) -> Option<(usize, usize)> {
+    let rest = if let Some(rest) = event.strip_prefix("test result: ") {
+        rest
+    } else if event.starts_with("Summary [") {
+        event.split_once(" run: ")?.1
+    } else {
+        return None;
+    };
+    let count = |label: &str| {
+        rest.split([';', ','])
+            .filter_map(|part| part.trim().strip_suffix(label))
+            .filter_map(|number| number.split_whitespace().last()?.parse::<usize>().ok())
+            .sum::<usize>()
+    };
+    Some((count("passed"), count("failed")))
+}
+
+/// Event headers and bookkeeping lines that say nothing about progress.
+fn is_noise(event: &str) -> bool {
+    matches!(
+        event,
+        "exec" | "codex" | "thinking" | "file update" | "tokens used" | "user" | "--------"
+    ) || event.starts_with("tokens used")
+        || event.starts_with("Success. Updated the following files")
+        || event.starts_with("apply_patch")
+        || event.starts_with("workdir:")
+        || event.starts_with("model:")
+        || event.starts_with("provider:")
+        || event.starts_with("approval:")
+        || event.starts_with("sandbox:")
+        || event.starts_with("reasoning")
+        || event.starts_with("session id:")
+        || event.starts_with("OpenAI Codex")
+        || event.starts_with("@@")
+        || event.starts_with('+')
+        || event.starts_with('-')
+        || event.chars().all(|c| c.is_ascii_digit() || c == ',')
+}
+
+fn listed(items: &[String]) -> String {
+    let mut listed = items
+        .iter()
+        .take(MAX_LISTED)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if items.len() > MAX_LISTED {
+        let _ = write!(listed, " and {} more", items.len() - MAX_LISTED);
+    }
+    listed
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+fn quote(text: &str) -> String {
+    if text.chars().count() <= MAX_QUOTE_LEN {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(MAX_QUOTE_LEN - 1).collect();
+    format!("{cut}…")
+}
@@ -0,0 +1,371 @@
+//! Scanner for the traceability doc-comment tags agents add to Rust sources:
+//! `@impl(R-...)`, `@s(S-...)`, `@contract(C-###)`, `@covers(R-...)` and `@tv(TV-...)`.
+
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use syn::{Attribute, ImplItem, Item, spanned::Spanned};
+
+use crate::records::spec::SpecClause;
+
+/// Crate directories scanned for tagged sources.
+const SOURCE_DIRS: [&str; 4] = ["src", "tests", "benches", "examples"];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum TagKind {
+    Impl,
+    Spec,
+    Contract,
+    Covers,
+    TestVector,
+}
+
+impl TagKind {
+    const ALL: [Self; 5] = [
+        Self::Impl,
+        Self::Spec,
+        Self::Contract,
+        Self::Covers,
+        Self::TestVector,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Impl => "impl",
+            Self::Spec => "s",
+            Self::Contract => "contract",
+            Self::Covers => "covers",
+            Self::TestVector => "tv",
+        }
+    }
+}
+
+/// The item a tag is attached to: the first declaration after its doc comment.
+#[derive(Clone, Debug)]
+pub(crate) struct TaggedItem {
+    pub(crate) name: String,
+    pub(crate) line: usize,
+    pub(crate) is_test: bool,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Tag {
+    pub(crate) kind: TagKind,
+    pub(crate) ids: Vec<String>,
+    /// Path relative to the scanned crate directory.
+    pub(crate) file: PathBuf,
+    pub(crate) line: usize,
+    /// `None` for inner (`//!`) tags that describe the enclosing module.
+    pub(crate) item: Option<TaggedItem>,
+}
+
+impl Tag {
+    pub(crate) fn mentions(&self, kind: TagKind, id: &str) -> bool {
+        self.kind == kind && self.ids.iter().any(|tagged| tagged == id)
+    }
+}
+
+/// A function or impl block tagged `@s(S-...)` and/or `@impl(R-...)`, with the
+/// source lines it spans.
+#[derive(Clone, Debug)]
+pub(crate) struct TaggedSpan {
+    /// Path relative to the scanned crate directory.
+    pub(crate) file: PathBuf,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) specs: Vec<String>,
+    pub(crate) requirements: Vec<String>,
+}
+
+impl TaggedSpan {
+    /// Items tagged `@s` count toward those clauses; items tagged only `@impl(R-...)`
+    /// count toward every clause that references one of those requirements.
+    pub(crate) fn traces_to(&self, clause: &SpecClause) -> bool {
+        self.specs.contains(&clause.id)
+            || (self.specs.is_empty()
+                && self
+                    .requirements
+                    .iter()
+                    .any(|r| clause.requirements.contains(r)))
+    }
+
+    pub(crate) fn contains(&self, file: &Path, line: usize) -> bool {
+        self.file == file && (self.start..=self.end).contains(&line)
+    }
+}
+
+/// Every `.rs` file under the crate's `src/`, `tests/`, `benches/` and `examples/`.
+pub(crate) fn rust_files(crate_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for dir in SOURCE_DIRS {
+        collect_rust_files(&crate_dir.join(dir), &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn collect_rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("failed to read directory {}", dir.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_rust_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Scan every Rust source in the crate for traceability tags.
+pub(crate) fn scan_crate(crate_dir: &Path) -> Result<Vec<Tag>> {
+    let mut tags = Vec::new();
+    for path in rust_files(crate_dir)? {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let relative = path.strip_prefix(crate_dir).unwrap_or(&path);
+        tags.extend(scan_source(relative, &content));
+    }
+    Ok(tags)
+}
+
+pub(crate) fn scan_source(file: &Path, content: &str) -> Vec<Tag> {
+    let mut tags = Vec::new();
+    // Outer tags waiting for the item their doc comment belongs to.
+    let mut pending: Vec<Tag> = Vec::new();
+    let mut saw_test_attr = false;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        let number = idx + 1;
+
+        if let Some(doc) = line.strip_prefix("//!") {
+            for (kind, ids) in parse_tags(doc) {
+                tags.push(Tag {
+                    kind,
+                    ids,
+                    file: file.to_path_buf(),
+                    line: number,
+                    item: None,
+                });
+            }
+            continue;
+        }
+
+        let doc = line
+            .strip_prefix("///")
+            .or_else(|| line.strip_prefix("#[doc"));
+        if let Some(doc) = doc {
+            for (kind, ids) in parse_tags(doc) {
+                pending.push(Tag {
+                    kind,
+                    ids,
+                    file: file.to_path_buf(),
+                    line: number,
+                    item: None,
+                });
+            }
+            continue;
+        }
+
+        if pending.is_empty() {
+            continue;
+        }
+
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+
+        if line.starts_with("#[") {
+            saw_test_attr |= is_test_attribute(line);
+            continue;
+        }
+
+        let item = item_name(line).map(|name| TaggedItem {
+            name,
+            line: number,
+            is_test: saw_test_attr,
+        });
+        for mut tag in pending.drain(..) {
+            tag.item.clone_from(&item);
+            tags.push(tag);
+        }
+        saw_test_attr = false;
+    }
+
+    tags.append(&mut pending);
+    tags
+}
+
+pub(crate) fn parse_tags(doc: &str) -> Vec<(TagKind, Vec<String>)> {
+    let mut found = Vec::new();
+    for kind in TagKind::ALL {
+        let needle = format!("@{}(", kind.name());
+        let mut rest = doc;
+        while let Some(start) = rest.find(&needle) {
+            let after = &rest[start + needle.len()..];
+            let Some(end) = after.find(')') else {
+                break;
+            };
+            let ids: Vec<String> = after[..end]
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect();
+            if !ids.is_empty() {
+                found.push((kind, ids));
+            }
+            rest = &after[end..];
+        }
+    }
+    found
+}
+
+/// Tags found in the `#[doc = "..."]` attributes (including `///` comments) of a parsed item.
+pub(crate) fn doc_tags(attrs: &[Attribute]) -> Vec<(TagKind, Vec<String>)> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(doc) => match &doc.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(text),
+                    ..
+                }) => Some(text.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .flat_map(|doc| parse_tags(&doc))
+        .collect()
+}
+
+fn is_test_attribute(line: &str) -> bool {
+    let attr = line.trim_start_matches("#[").trim_end_matches(']');
+    attr == "test" || attr.ends_with("::test") || attr.starts_with("test_case")
+}
+
+/// Name of the item declared on `line`, e.g. `fn parse` -> `parse`.
+fn item_name(line: &str) -> Option<String> {
+    const KEYWORDS: [&str; 9] = [
+        "fn", "struct", "enum", "trait", "type", "const", "static", "mod", "union",
+    ];
+
+    let mut words = line
+        .split(|c: char| {
+            c.is_whitespace() || c == '(' || c == '<' || c == ':' || c == '{' || c == ';'
+        })
+        .filter(|word| !word.is_empty());
+
+    while let Some(word) = words.next() {
+        if word == "impl" {
+            let rest = line.split_once("impl")?.1;
+            let rest = rest.trim_start();
+            let rest = if rest.starts_with('<') {
+                rest.split_once('>').map_or(rest, |(_, after)| after)
+            } else {
+                rest
+            };
+            let header = rest.split('{').next().unwrap_or(rest).trim();
+            return Some(format!("impl {header}"));
+        }
+        if KEYWORDS.contains(&word) {
+            return words.next().map(str::to_string);
+        }
+    }
+    None
+}
+
+/// Tagged non-test functions, impl blocks and impl methods under the crate's `src/`.
+pub(crate) fn tagged_spans(crate_dir: &Path) -> Result<Vec<TaggedSpan>> {
+    let mut spans = Vec::new();
+    for path in rust_files(crate_dir)? {
+        let relative = path.strip_prefix(crate_dir).unwrap_or(&path).to_path_buf();
+        if !relative.starts_with("src") {
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let Ok(file) = syn::parse_file(&content) else {
+            continue;
+        };
+        collect_spans(&file.items, &relative, &mut spans);
+    }
+    Ok(spans)
+}
+
+/// Record tagged functions, impl blocks and impl methods; test code is skipped.
+fn collect_spans(items: &[Item], file: &Path, spans: &mut Vec<TaggedSpan>) {
+    let mut push = |attrs: &[Attribute], node: &dyn Spanned| {
+        let (specs, requirements) = span_tags(attrs);
+        if specs.is_empty() && requirements.is_empty() {
+            return;
+        }
+        let span = node.span();
+        spans.push(TaggedSpan {
+            file: file.to_path_buf(),
+            start: span.start().line,
+            end: span.end().line,
+            specs,
+            requirements,
+        });
+    };
+
+    let mut nested = Vec::new();
+    for item in items {
+        match item {
+            Item::Fn(item) if !is_test_code(&item.attrs) => push(&item.attrs, item),
+            Item::Impl(item) if !is_test_code(&item.attrs) => {
+                push(&item.attrs, item);
+                for member in &item.items {
+                    if let ImplItem::Fn(method) = member
+                        && !is_test_code(&method.attrs)
+                    {
+                        push(&method.attrs, method);
+                    }
+                }
+            }
+            Item::Mod(item) if !is_test_code(&item.attrs) => {
+                if let Some((_, items)) = &item.content {
+                    nested.push(items);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for items in nested {
+        collect_spans(items, file, spans);
+    }
+}
+
+fn is_test_code(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let path = attr.path();
+        path.is_ident("test")
+            || (path.is_ident("cfg")
+                && attr
+                    .parse_args::<syn::Ident>()
+                    .is_ok_and(|ident| ident == "test"))
+    })
+}
+
+fn span_tags(attrs: &[Attribute]) -> (Vec<String>, Vec<String>) {
+    let mut specs = Vec::new();
+    let mut requirements = Vec::new();
+    for (kind, ids) in doc_tags(attrs) {
+        match kind {
+            TagKind::Spec => specs.extend(ids),
+            TagKind::Impl => requirements.extend(ids),
+            _ => {}
+        }
+    }
+    (specs, requirements)
+}
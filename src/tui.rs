@@ -0,0 +1,373 @@
+//! Optional terminal dashboard (`--tui`) for the reviewer/builder loops.
+//!
+//! A render thread owns the terminal and draws a shared [`Status`]; the loop updates
+//! it through the free functions below. While the dashboard is active, log lines and
+//! verbatim Codex output are appended to its agent log instead of being printed.
+
+use anyhow::{Result, anyhow};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Paragraph, Wrap},
+};
+use std::{
+    io::{self, IsTerminal},
+    process,
+    sync::{
+        Arc, Mutex, MutexGuard, OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::logging;
+
+/// Agent log lines kept for scrolling.
+const MAX_LOG_LINES: usize = 10_000;
+
+/// Log lines replayed on the normal screen after the dashboard closes.
+const TAIL_ON_EXIT: usize = 20;
+
+/// The only role whose remaining iterations `s` can skip.
+const BUILDER_ROLE: &str = "builder";
+
+const INPUT_POLL: Duration = Duration::from_millis(100);
+
+static STATUS: OnceLock<Mutex<Status>> = OnceLock::new();
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Everything the dashboard shows, plus the key requests the loop acts on.
+struct Status {
+    workflow: &'static str,
+    role: String,
+    review_cycle: usize,
+    max_review_cycles: usize,
+    builder_iter: usize,
+    max_builder_iters: usize,
+    remaining_work: String,
+    ci: String,
+    started: Instant,
+    tokens: u64,
+    log: Vec<String>,
+    /// Lines scrolled back from the bottom of the log; 0 follows new output.
+    scroll_back: usize,
+    pause: bool,
+    skip: bool,
+    abort: bool,
+}
+
+/// Owns the render thread; dropping it restores the terminal.
+pub(crate) struct Dashboard {
+    stop: Arc<AtomicBool>,
+    render: Option<JoinHandle<()>>,
+}
+
+impl Dashboard {
+    pub(crate) fn start(
+        workflow: &'static str,
+        max_review_cycles: usize,
+        max_builder_iters: usize,
+    ) -> Result<Self> {
+        if !io::stdout().is_terminal() {
+            return Err(anyhow!("--tui requires stdout to be a terminal"));
+        }
+
+        let status = Status {
+            workflow,
+            role: "starting".to_string(),
+            review_cycle: 0,
+            max_review_cycles,
+            builder_iter: 0,
+            max_builder_iters,
+            remaining_work: String::new(),
+            ci: "pending".to_string(),
+            started: Instant::now(),
+            tokens: 0,
+            log: Vec::new(),
+            scroll_back: 0,
+            pause: false,
+            skip: false,
+            abort: false,
+        };
+        if STATUS.set(Mutex::new(status)).is_err() {
+            return Err(anyhow!("the dashboard can only be started once"));
+        }
+
+        let terminal = ratatui::try_init()?;
+        ACTIVE.store(true, Ordering::SeqCst);
+        let stop = Arc::new(AtomicBool::new(false));
+        let render = {
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || render_loop(terminal, &stop))
+        };
+
+        Ok(Self {
+            stop,
+            render: Some(render),
+        })
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(render) = self.render.take() {
+            let _ = render.join();
+        }
+        ACTIVE.store(false, Ordering::SeqCst);
+
+        // The alternate screen is gone; keep the last lines visible.
+        if let Some(status) = status() {
+            let start = status.log.len().saturating_sub(TAIL_ON_EXIT);
+            for line in &status.log[start..] {
+                eprintln!("{line}");
+            }
+        }
+    }
+}
+
+pub(crate) fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Print `text` to stdout, or to the agent log while the dashboard is active.
+pub(crate) fn echo(text: &str) {
+    if is_active() {
+        log(text);
+    } else {
+        println!("{text}");
+    }
+}
+
+/// Append `text` (possibly several lines) to the agent log.
+pub(crate) fn log(text: &str) {
+    let Some(mut status) = status() else {
+        return;
+    };
+    status.log.extend(
+        text.lines()
+            .map(|line| logging::strip_ansi(line.trim_end_matches('\r'))),
+    );
+    let excess = status.log.len().saturating_sub(MAX_LOG_LINES);
+    status.log.drain(..excess);
+}
+
+/// Add the usage Codex reports as `tokens used: 1,234` (or on the following line).
+pub(crate) fn record_usage(output: &str) {
+    let Some(mut status) = status() else {
+        return;
+    };
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        let Some((_, rest)) = line.split_once("tokens used") else {
+            continue;
+        };
+        let count = digits(rest).or_else(|| lines.next().and_then(digits));
+        status.tokens += count.unwrap_or(0);
+    }
+}
+
+/// Skips only apply to builder steps; a request left over from one is dropped here.
+pub(crate) fn set_step(role: &str, review_cycle: usize, builder_iter: usize) {
+    if let Some(mut status) = status() {
+        if role != BUILDER_ROLE {
+            status.skip = false;
+        }
+        status.role = role.to_string();
+        status.review_cycle = review_cycle;
+        status.builder_iter = builder_iter;
+    }
+}
+
+/// Change the role without touching the cycle and iteration counters.
+pub(crate) fn set_role(role: &str) {
+    if let Some(mut status) = status() {
+        if role != BUILDER_ROLE {
+            status.skip = false;
+        }
+        status.role = role.to_string();
+    }
+}
+
+pub(crate) fn set_remaining_work(text: &str) {
+    if let Some(mut status) = status() {
+        status.remaining_work = text.to_string();
+    }
+}
+
+pub(crate) fn set_ci(text: &str) {
+    if let Some(mut status) = status() {
+        status.ci = text.to_string();
+    }
+}
+
+/// Between steps: wait while paused, and fail once an abort was requested.
+pub(crate) fn checkpoint() -> Result<()> {
+    loop {
+        let Some(status) = status() else {
+            return Ok(());
+        };
+        if status.abort {
+            return Err(anyhow!("aborted from the dashboard"));
+        }
+        if !status.pause {
+            return Ok(());
+        }
+        drop(status);
+        thread::sleep(INPUT_POLL);
+    }
+}
+
+/// Whether a skip to the next reviewer pass was requested; clears the request.
+pub(crate) fn take_skip() -> bool {
+    status().is_some_and(|mut status| std::mem::take(&mut status.skip))
+}
+
+fn status() -> Option<MutexGuard<'static, Status>> {
+    STATUS.get().map(|status| {
+        status
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    })
+}
+
+fn render_loop(mut terminal: DefaultTerminal, stop: &AtomicBool) {
+    while !stop.load(Ordering::SeqCst) {
+        if let Some(status) = status() {
+            let _ = terminal.draw(|frame| draw(frame, &status));
+        }
+        if event::poll(INPUT_POLL).unwrap_or(false)
+            && let Ok(Event::Key(key)) = event::read()
+            && key.kind == KeyEventKind::Press
+        {
+            if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                // Raw mode swallows SIGINT; exit immediately like an interrupted run.
+                ratatui::restore();
+                process::exit(130);
+            }
+            if let Some(mut status) = status() {
+                handle_key(&mut status, key.code);
+            }
+        }
+    }
+    ratatui::restore();
+}
+
+fn handle_key(status: &mut Status, code: KeyCode) {
+    match code {
+        KeyCode::Char('p') => status.pause = !status.pause,
+        KeyCode::Char('s') if status.role == BUILDER_ROLE => status.skip = true,
+        KeyCode::Char('q') => status.abort = true,
+        KeyCode::Up | KeyCode::Char('k') => status.scroll_back += 1,
+        KeyCode::Down | KeyCode::Char('j') => {
+            status.scroll_back = status.scroll_back.saturating_sub(1);
+        }
+        KeyCode::PageUp => status.scroll_back += 20,
+        KeyCode::PageDown => status.scroll_back = status.scroll_back.saturating_sub(20),
+        KeyCode::End => status.scroll_back = 0,
+        _ => {}
+    }
+    status.scroll_back = status.scroll_back.min(status.log.len());
+}
+
+fn draw(frame: &mut Frame, status: &Status) {
+    let [header, panes, log_area, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Percentage(35),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [work_area, ci_area] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(panes);
+
+    let elapsed = status.started.elapsed().as_secs();
+    let mut summary = format!(
+        "role: {} | review cycle {}/{} | builder iteration {}/{} | elapsed {:02}:{:02}:{:02} | tokens {}",
+        status.role,
+        status.review_cycle,
+        status.max_review_cycles,
+        status.builder_iter,
+        status.max_builder_iters,
+        elapsed / 3600,
+        elapsed / 60 % 60,
+        elapsed % 60,
+        status.tokens
+    );
+    for (requested, label) in [
+        (status.pause, "PAUSE after this step"),
+        (status.skip, "SKIP to reviewer"),
+        (status.abort, "ABORT after this step"),
+    ] {
+        if requested {
+            summary.push_str(" | ");
+            summary.push_str(label);
+        }
+    }
+    frame.render_widget(
+        Paragraph::new(summary)
+            .block(Block::bordered().title(format!(" blueprints {} ", status.workflow))),
+        header,
+    );
+
+    let remaining = if status.remaining_work.is_empty() {
+        "none yet"
+    } else {
+        status.remaining_work.as_str()
+    };
+    frame.render_widget(
+        Paragraph::new(remaining)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(" Remaining work ")),
+        work_area,
+    );
+    frame.render_widget(
+        Paragraph::new(status.ci.as_str())
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(" CI ")),
+        ci_area,
+    );
+
+    draw_log(frame, status, log_area);
+
+    frame.render_widget(
+        Line::from("p pause/resume  s skip to reviewer  q abort  ↑↓/PgUp/PgDn scroll  End follow  Ctrl-C exit now")
+            .style(Style::default().fg(Color::DarkGray)),
+        footer,
+    );
+}
+
+fn draw_log(frame: &mut Frame, status: &Status, area: Rect) {
+    let height = usize::from(area.height.saturating_sub(2));
+    let end = status.log.len().saturating_sub(status.scroll_back);
+    let start = end.saturating_sub(height);
+    let lines: Vec<Line> = status.log[start..end]
+        .iter()
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+
+    let title = if status.scroll_back == 0 {
+        " Agent log ".to_string()
+    } else {
+        format!(" Agent log (scrolled back {} lines) ", status.scroll_back)
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(title.bold())),
+        area,
+    );
+}
+
+/// The number at the start of `text`, ignoring `:` and thousands separators.
+fn digits(text: &str) -> Option<u64> {
+    let number: String = text
+        .trim_start_matches([':', ' '])
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == ',')
+        .filter(char::is_ascii_digit)
+        .collect();
+    number.parse().ok()
+}